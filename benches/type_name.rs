@@ -0,0 +1,78 @@
+//! Compares [`type_name`] pre-allocating its buffer with the length hint
+//! against writing the name into an unreserved buffer and against
+//! [`with_type_name`] reusing a thread-local buffer.
+//!
+//! Run with `cargo bench`.
+
+use std::{
+	alloc::{GlobalAlloc, Layout, System},
+	hint::black_box,
+	sync::atomic::{AtomicUsize, Ordering},
+	time::Instant,
+};
+use tyname::{type_name, with_type_name, TypeName};
+
+/// Global allocator counting the allocations and reallocations.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+		System.alloc(layout)
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		System.dealloc(ptr, layout)
+	}
+
+	unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+		System.realloc(ptr, layout, new_size)
+	}
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+type Nested = Box<Vec<Result<i32, String>>>;
+
+/// Runs `f` repeatedly and prints its mean time and allocations per iteration.
+fn bench<F>(label: &str, mut f: F)
+where
+	F: FnMut(),
+{
+	const ITERATIONS: u32 = 1_000_000;
+
+	for _ in 0..ITERATIONS / 10 {
+		f();
+	}
+	let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+	let start = Instant::now();
+	for _ in 0..ITERATIONS {
+		f();
+	}
+	let elapsed = start.elapsed();
+	let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+	println!(
+		"{:<28} {:>8.1} ns/iter {:>6.2} allocs/iter",
+		label,
+		elapsed.as_nanos() as f64 / f64::from(ITERATIONS),
+		allocations as f64 / f64::from(ITERATIONS),
+	);
+}
+
+fn main() {
+	bench("type_name", || {
+		black_box(type_name::<Nested>());
+	});
+	bench("unreserved write_type_name", || {
+		let mut buffer = String::new();
+		Nested::write_type_name(&mut buffer).unwrap();
+		black_box(buffer);
+	});
+	bench("with_type_name", || {
+		black_box(with_type_name::<Nested, _>(|name| name.len()));
+	});
+}
@@ -0,0 +1,96 @@
+//! Expansion of `#[derive(DebugTypeName)]`.
+
+use crate::parse::{Data, Fields, Input};
+
+/// Expands to a `Debug` impl that uses the type name of `Self`.
+pub fn expand(input: &Input) -> String {
+	let predicates = input
+		.type_params()
+		.map(|param| format!("{}: ::core::fmt::Debug", param.name))
+		.chain(Some(String::from("Self: ::tyname::TypeName")));
+	let arms = match &input.data {
+		Data::Struct(fields) => format!(
+			"Self{} => {{ {} }}",
+			pattern(fields),
+			debug_fields(fields),
+		),
+		Data::Enum(variants) => variants
+			.iter()
+			.map(|variant| {
+				format!(
+					"Self::{ident}{pattern} => {{ __f.write_str(\"::{ident}\")?; {debug} }}",
+					ident = variant.ident,
+					pattern = pattern(&variant.fields),
+					debug = debug_fields(&variant.fields),
+				)
+			})
+			.collect::<Vec<_>>()
+			.join(" "),
+	};
+	format!(
+		"impl{impl_generics} ::core::fmt::Debug for {ident}{ty_generics} {where_clause} {{
+			fn fmt(&self, __f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {{
+				::tyname::debug_with_type_name::<Self>(__f)?;
+				match *self {{ {arms} }}
+			}}
+		}}",
+		impl_generics = input.impl_generics(),
+		ident = input.ident,
+		ty_generics = input.ty_generics(),
+		where_clause = input.where_clause(predicates),
+		arms = arms,
+	)
+}
+
+/// Returns a pattern binding all fields by reference to `__field_N`.
+fn pattern(fields: &Fields) -> String {
+	match fields {
+		Fields::Named(fields) => {
+			let bindings = fields
+				.iter()
+				.enumerate()
+				.map(|(n, field)| {
+					format!("{}: ref __field_{}", field.ident.as_ref().unwrap(), n)
+				})
+				.collect::<Vec<_>>();
+			format!(" {{ {} }}", bindings.join(", "))
+		}
+		Fields::Unnamed(fields) => {
+			let bindings = (0..fields.len())
+				.map(|n| format!("ref __field_{}", n))
+				.collect::<Vec<_>>();
+			format!("({})", bindings.join(", "))
+		}
+		Fields::Unit => String::new(),
+	}
+}
+
+/// Returns an expression debug-formatting the bound fields after the written name.
+///
+/// The builders are given an empty name since the name is already written.
+fn debug_fields(fields: &Fields) -> String {
+	match fields {
+		Fields::Named(fields) => {
+			let entries = fields
+				.iter()
+				.enumerate()
+				.map(|(n, field)| {
+					let ident = field.ident.as_ref().unwrap();
+					let ident = ident.trim_start_matches("r#");
+					format!(".field(\"{}\", __field_{})", ident, n)
+				})
+				.collect::<String>();
+			format!("__f.debug_struct(\"\"){}.finish()", entries)
+		}
+		Fields::Unnamed(fields) if fields.len() == 1 => {
+			String::from("::tyname::__debug_single_field(__f, __field_0)")
+		}
+		Fields::Unnamed(fields) => {
+			let entries = (0..fields.len())
+				.map(|n| format!(".field(__field_{})", n))
+				.collect::<String>();
+			format!("__f.debug_tuple(\"\"){}.finish()", entries)
+		}
+		Fields::Unit => String::from("::core::result::Result::Ok(())"),
+	}
+}
@@ -0,0 +1,80 @@
+//! Derive macros for the `tyname` crate.
+//!
+//! Use them through the `derive` feature of the `tyname` crate.
+
+extern crate proc_macro;
+
+mod debug;
+mod parse;
+mod structural;
+mod type_name;
+
+use crate::parse::Input;
+use proc_macro::TokenStream;
+
+/// Derives `TypeName` writing the name of the item followed by its generic arguments.
+///
+/// Every generic type parameter is required to implement `TypeName`.
+/// Lifetime parameters are not written while the values of const
+/// parameters are.
+///
+/// The derived implementation only ever writes the name of the item and
+/// its generic arguments, it never visits the types of the fields.
+/// Therefore recursive types such as `struct Node { next: Option<Box<Node>> }`
+/// have a finite name, e.g. `Node`.
+///
+/// # Options
+///
+/// - `#[tyname(structural)]`: Also derives `StructuralTypeName` which writes
+///   the name followed by the names and types of all fields, e.g.
+///   `Point{x:f32,y:f32}`. Requires all field types to implement `TypeName`.
+/// - `#[tyname(skip_generics)]`: Writes only the name of the item without
+///   its generic arguments, e.g. `Handle` for `Handle<u32>`. The generic
+///   type parameters are then not required to implement `TypeName`.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(TypeName)]
+/// struct Wrapper<'a, T, const N: usize>(&'a [T; N]);
+///
+/// assert_eq!(type_name::<Wrapper<u8, 4>>(), "Wrapper<u8, 4>");
+/// ```
+#[proc_macro_derive(TypeName, attributes(tyname))]
+pub fn derive_type_name(input: TokenStream) -> TokenStream {
+	expand(input, |input| {
+		let mut expanded = type_name::expand(input);
+		if input.options.structural {
+			expanded.push_str(&structural::expand(input));
+		}
+		expanded
+	})
+}
+
+/// Derives `Debug` printing the `tyname` type name of `Self` followed by its fields.
+///
+/// Requires `Self` to implement `TypeName`. The name is written with
+/// `tyname::debug_with_type_name` and thus is not allocated.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(DebugTypeName)]
+/// struct Wrapper<T>(T);
+///
+/// assert_eq!(format!("{:?}", Wrapper(42_u32)), "Wrapper<u32>(42)");
+/// ```
+#[proc_macro_derive(DebugTypeName)]
+pub fn derive_debug_type_name(input: TokenStream) -> TokenStream {
+	expand(input, debug::expand)
+}
+
+/// Parses the input and expands it with the given expansion function.
+fn expand(input: TokenStream, f: fn(&Input) -> String) -> TokenStream {
+	match Input::parse(input) {
+		Ok(input) => f(&input)
+			.parse()
+			.expect("[tyname-derive] Generated invalid code"),
+		Err(error) => error.into_compile_error(),
+	}
+}
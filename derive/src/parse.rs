@@ -0,0 +1,541 @@
+//! A minimal parser for the items accepted by the derive macros.
+//!
+//! The derives only need to know the name, the generics and the shape
+//! of the fields of the input item so we can get away without `syn`.
+
+use proc_macro::{Delimiter, Group, Spacing, TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+/// An error encountered while parsing the input of a derive.
+pub struct Error(String);
+
+impl Error {
+	/// Creates a new error with the given message.
+	pub fn new<S>(message: S) -> Self
+	where
+		S: Into<String>,
+	{
+		Error(message.into())
+	}
+
+	/// Converts the error into a `compile_error!` invocation.
+	pub fn into_compile_error(self) -> TokenStream {
+		format!("::core::compile_error!({:?});", self.0)
+			.parse()
+			.expect("[tyname-derive] Failed to create compile error")
+	}
+}
+
+/// The result type of the parser.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The options given via `#[tyname(...)]` attributes.
+#[derive(Default)]
+pub struct Options {
+	/// Also derive `StructuralTypeName`.
+	pub structural: bool,
+	/// Write only the name of the item without its generic arguments.
+	pub skip_generics: bool,
+}
+
+/// A parsed `struct` or `enum` definition.
+pub struct Input {
+	/// The options given via `#[tyname(...)]` attributes on the item.
+	pub options: Options,
+	/// The name of the item.
+	pub ident: String,
+	/// The generic parameters of the item.
+	pub generics: Vec<GenericParam>,
+	/// The predicates of the where clause of the item.
+	pub where_predicates: Vec<String>,
+	/// The fields or variants of the item.
+	pub data: Data,
+}
+
+/// The kind of a generic parameter.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum GenericParamKind {
+	Lifetime,
+	Type,
+	Const,
+}
+
+/// A generic parameter of the parsed item.
+pub struct GenericParam {
+	/// The kind of the generic parameter.
+	pub kind: GenericParamKind,
+	/// The name of the generic parameter, e.g. `'a`, `T` or `N`.
+	pub name: String,
+	/// The declaration of the generic parameter without its default,
+	/// e.g. `'a: 'b`, `T: Clone` or `const N: usize`.
+	pub decl: String,
+}
+
+/// The fields or variants of the parsed item.
+pub enum Data {
+	Struct(Fields),
+	Enum(Vec<Variant>),
+}
+
+/// The fields of a struct or enum variant.
+pub enum Fields {
+	Named(Vec<Field>),
+	Unnamed(Vec<Field>),
+	Unit,
+}
+
+/// A single field of a struct or enum variant.
+pub struct Field {
+	/// The name of the field, `None` for tuple fields.
+	pub ident: Option<String>,
+	/// The type of the field.
+	pub ty: String,
+}
+
+/// A single variant of an enum.
+pub struct Variant {
+	/// The name of the variant.
+	pub ident: String,
+	/// The fields of the variant.
+	pub fields: Fields,
+}
+
+impl Input {
+	/// Parses the item a derive has been applied to.
+	pub fn parse(input: TokenStream) -> Result<Self> {
+		let tokens = Vec::from_iter(input);
+		let mut cursor = Cursor::new(&tokens);
+		let options = parse_options(&cursor.parse_attributes())?;
+		cursor.skip_visibility();
+		let kind = cursor.expect_ident()?;
+		let ident = cursor.expect_ident()?;
+		let generics = if cursor.peek_punct('<') {
+			parse_generics(&cursor.take_angle_bracketed()?)?
+		} else {
+			Vec::new()
+		};
+		let mut where_predicates = cursor.parse_where_clause();
+		let data = match kind.as_str() {
+			"struct" => {
+				let fields = match cursor.next() {
+					Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+						Fields::Named(parse_named_fields(group.stream())?)
+					}
+					Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+						let fields = Fields::Unnamed(parse_unnamed_fields(group.stream()));
+						where_predicates.extend(cursor.parse_where_clause());
+						fields
+					}
+					Some(TokenTree::Punct(ref punct)) if punct.as_char() == ';' => Fields::Unit,
+					_ => return Err(Error::new("expected struct fields")),
+				};
+				Data::Struct(fields)
+			}
+			"enum" => match cursor.next() {
+				Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+					Data::Enum(parse_variants(group.stream())?)
+				}
+				_ => return Err(Error::new("expected enum variants")),
+			},
+			"union" => return Err(Error::new("unions are not supported")),
+			_ => return Err(Error::new("expected `struct` or `enum`")),
+		};
+		Ok(Input {
+			options,
+			ident,
+			generics,
+			where_predicates,
+			data,
+		})
+	}
+
+	/// Returns the generic parameters of the item that are types.
+	pub fn type_params(&self) -> impl Iterator<Item = &GenericParam> {
+		self.generics
+			.iter()
+			.filter(|param| param.kind == GenericParamKind::Type)
+	}
+
+	/// Returns the generic parameters for an `impl` header, e.g. `<'a, T: Clone>`.
+	pub fn impl_generics(&self) -> String {
+		if self.generics.is_empty() {
+			return String::new();
+		}
+		let decls = self
+			.generics
+			.iter()
+			.map(|param| param.decl.as_str())
+			.collect::<Vec<_>>();
+		format!("<{}>", decls.join(", "))
+	}
+
+	/// Returns the generic arguments of the item's type, e.g. `<'a, T>`.
+	pub fn ty_generics(&self) -> String {
+		if self.generics.is_empty() {
+			return String::new();
+		}
+		let names = self
+			.generics
+			.iter()
+			.map(|param| param.name.as_str())
+			.collect::<Vec<_>>();
+		format!("<{}>", names.join(", "))
+	}
+
+	/// Returns the where clause of the item extended by the given predicates.
+	pub fn where_clause<I>(&self, extra: I) -> String
+	where
+		I: IntoIterator<Item = String>,
+	{
+		let predicates = self
+			.where_predicates
+			.iter()
+			.cloned()
+			.chain(extra)
+			.collect::<Vec<_>>();
+		if predicates.is_empty() {
+			return String::new();
+		}
+		format!("where {}", predicates.join(", "))
+	}
+}
+
+/// A cursor over a slice of token trees.
+struct Cursor<'a> {
+	tokens: &'a [TokenTree],
+	pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+	fn new(tokens: &'a [TokenTree]) -> Self {
+		Cursor { tokens, pos: 0 }
+	}
+
+	fn peek(&self) -> Option<&'a TokenTree> {
+		self.tokens.get(self.pos)
+	}
+
+	fn next(&mut self) -> Option<&'a TokenTree> {
+		let token = self.peek();
+		self.pos += 1;
+		token
+	}
+
+	fn rest(&self) -> &'a [TokenTree] {
+		&self.tokens[self.pos.min(self.tokens.len())..]
+	}
+
+	fn peek_punct(&self, ch: char) -> bool {
+		match self.peek() {
+			Some(TokenTree::Punct(punct)) => punct.as_char() == ch,
+			_ => false,
+		}
+	}
+
+	fn peek_ident(&self, name: &str) -> bool {
+		match self.peek() {
+			Some(TokenTree::Ident(ident)) => ident.to_string() == name,
+			_ => false,
+		}
+	}
+
+	fn expect_ident(&mut self) -> Result<String> {
+		match self.next() {
+			Some(TokenTree::Ident(ident)) => Ok(ident.to_string()),
+			_ => Err(Error::new("expected identifier")),
+		}
+	}
+
+	/// Parses all outer attributes, e.g. `#[doc = "..."]`.
+	fn parse_attributes(&mut self) -> Vec<Group> {
+		let mut attrs = Vec::new();
+		while self.peek_punct('#') {
+			match self.tokens.get(self.pos + 1) {
+				Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => {
+					attrs.push(group.clone());
+					self.pos += 2;
+				}
+				_ => break,
+			}
+		}
+		attrs
+	}
+
+	/// Skips a visibility, e.g. `pub` or `pub(crate)`.
+	fn skip_visibility(&mut self) {
+		if !self.peek_ident("pub") {
+			return;
+		}
+		self.pos += 1;
+		if let Some(TokenTree::Group(group)) = self.peek() {
+			if group.delimiter() == Delimiter::Parenthesis && is_visibility_restriction(group) {
+				self.pos += 1;
+			}
+		}
+	}
+
+	/// Takes the tokens between the current `<` and its matching `>`.
+	fn take_angle_bracketed(&mut self) -> Result<Vec<TokenTree>> {
+		self.pos += 1;
+		let mut depth = 0usize;
+		let mut tokens = Vec::new();
+		while let Some(token) = self.next() {
+			match angle_bracket(token, tokens.last()) {
+				Some(AngleBracket::Open) => depth += 1,
+				Some(AngleBracket::Close) if depth == 0 => return Ok(tokens),
+				Some(AngleBracket::Close) => depth -= 1,
+				None => (),
+			}
+			tokens.push(token.clone());
+		}
+		Err(Error::new("unclosed generic parameter list"))
+	}
+
+	/// Parses the predicates of an optional where clause.
+	///
+	/// Stops at the item body or the terminating semicolon.
+	fn parse_where_clause(&mut self) -> Vec<String> {
+		if !self.peek_ident("where") {
+			return Vec::new();
+		}
+		self.pos += 1;
+		let start = self.pos;
+		while let Some(token) = self.peek() {
+			match token {
+				TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => break,
+				TokenTree::Punct(punct) if punct.as_char() == ';' => break,
+				_ => self.pos += 1,
+			}
+		}
+		split_top_level(&self.tokens[start..self.pos])
+			.into_iter()
+			.map(to_string)
+			.collect()
+	}
+}
+
+/// Parses the options of all `#[tyname(...)]` attributes.
+fn parse_options(attrs: &[Group]) -> Result<Options> {
+	let mut options = Options::default();
+	for attr in attrs {
+		let tokens = Vec::from_iter(attr.stream());
+		let args = match tokens.as_slice() {
+			[TokenTree::Ident(ident), TokenTree::Group(args)]
+				if ident.to_string() == "tyname" && args.delimiter() == Delimiter::Parenthesis =>
+			{
+				Vec::from_iter(args.stream())
+			}
+			_ => continue,
+		};
+		for arg in split_top_level(&args) {
+			match to_string(arg).as_str() {
+				"structural" => options.structural = true,
+				"skip_generics" => options.skip_generics = true,
+				unknown => {
+					return Err(Error::new(format!("unknown tyname option `{}`", unknown)))
+				}
+			}
+		}
+	}
+	Ok(options)
+}
+
+/// Returns `true` if the group is the restriction of a visibility such as `(crate)`.
+fn is_visibility_restriction(group: &Group) -> bool {
+	match group.stream().into_iter().next() {
+		Some(TokenTree::Ident(ident)) => {
+			matches!(ident.to_string().as_str(), "crate" | "self" | "super" | "in")
+		}
+		_ => false,
+	}
+}
+
+enum AngleBracket {
+	Open,
+	Close,
+}
+
+/// Classifies the token as angle bracket given its preceding token.
+///
+/// The `>` of an arrow (`->`) is not a closing angle bracket.
+fn angle_bracket(token: &TokenTree, prev: Option<&TokenTree>) -> Option<AngleBracket> {
+	match token {
+		TokenTree::Punct(punct) if punct.as_char() == '<' => Some(AngleBracket::Open),
+		TokenTree::Punct(punct) if punct.as_char() == '>' => match prev {
+			Some(TokenTree::Punct(prev))
+				if prev.as_char() == '-' && prev.spacing() == Spacing::Joint =>
+			{
+				None
+			}
+			_ => Some(AngleBracket::Close),
+		},
+		_ => None,
+	}
+}
+
+/// Splits the tokens at all commas that are not nested within angle brackets.
+///
+/// A trailing comma does not produce an empty trailing segment.
+fn split_top_level(tokens: &[TokenTree]) -> Vec<&[TokenTree]> {
+	split_segments(tokens, false)
+}
+
+/// Splits the fields or variants at all commas that are not nested within
+/// angle brackets.
+///
+/// Unlike [`split_top_level`] the tokens following a top-level `=` are an
+/// expression, e.g. the discriminant `1 << 2` of a variant, in which `<`
+/// and `>` are operators instead of angle brackets.
+fn split_items(tokens: &[TokenTree]) -> Vec<&[TokenTree]> {
+	split_segments(tokens, true)
+}
+
+/// Splits the tokens at all top-level commas, see [`split_items`].
+fn split_segments(tokens: &[TokenTree], expr_after_eq: bool) -> Vec<&[TokenTree]> {
+	let mut segments = Vec::new();
+	let mut depth = 0usize;
+	let mut in_expr = false;
+	let mut start = 0;
+	for (n, token) in tokens.iter().enumerate() {
+		let prev = if n == 0 { None } else { tokens.get(n - 1) };
+		if !in_expr {
+			match angle_bracket(token, prev) {
+				Some(AngleBracket::Open) => depth += 1,
+				Some(AngleBracket::Close) => depth = depth.saturating_sub(1),
+				None => (),
+			}
+		}
+		if let TokenTree::Punct(punct) = token {
+			match punct.as_char() {
+				',' if depth == 0 => {
+					segments.push(&tokens[start..n]);
+					start = n + 1;
+					in_expr = false;
+				}
+				'=' if depth == 0 && expr_after_eq => {
+					in_expr = true;
+				}
+				_ => (),
+			}
+		}
+	}
+	if start < tokens.len() {
+		segments.push(&tokens[start..]);
+	}
+	segments
+}
+
+/// Returns the tokens up to the first top-level `=`, e.g. to strip defaults.
+fn strip_default(tokens: &[TokenTree]) -> &[TokenTree] {
+	let mut depth = 0usize;
+	for (n, token) in tokens.iter().enumerate() {
+		let prev = if n == 0 { None } else { tokens.get(n - 1) };
+		match angle_bracket(token, prev) {
+			Some(AngleBracket::Open) => depth += 1,
+			Some(AngleBracket::Close) => depth = depth.saturating_sub(1),
+			None => (),
+		}
+		if let TokenTree::Punct(punct) = token {
+			if punct.as_char() == '=' && depth == 0 {
+				return &tokens[..n];
+			}
+		}
+	}
+	tokens
+}
+
+/// Converts the tokens back into their source representation.
+fn to_string(tokens: &[TokenTree]) -> String {
+	TokenStream::from_iter(tokens.iter().cloned()).to_string()
+}
+
+/// Parses the generic parameters found between `<` and `>`.
+fn parse_generics(tokens: &[TokenTree]) -> Result<Vec<GenericParam>> {
+	split_top_level(tokens)
+		.into_iter()
+		.map(|param| {
+			let mut cursor = Cursor::new(param);
+			// Attributes on generic parameters are irrelevant for us.
+			cursor.parse_attributes();
+			let param = cursor.rest();
+			let (kind, name) = match param {
+				[TokenTree::Punct(punct), TokenTree::Ident(ident), ..] if punct.as_char() == '\'' => {
+					(GenericParamKind::Lifetime, format!("'{}", ident))
+				}
+				[TokenTree::Ident(keyword), TokenTree::Ident(ident), ..] if keyword.to_string() == "const" => {
+					(GenericParamKind::Const, ident.to_string())
+				}
+				[TokenTree::Ident(ident), ..] => (GenericParamKind::Type, ident.to_string()),
+				_ => return Err(Error::new("expected generic parameter")),
+			};
+			Ok(GenericParam {
+				kind,
+				name,
+				decl: to_string(strip_default(param)),
+			})
+		})
+		.collect()
+}
+
+/// Parses the fields of a struct or variant with named fields.
+fn parse_named_fields(stream: TokenStream) -> Result<Vec<Field>> {
+	let tokens = Vec::from_iter(stream);
+	split_items(&tokens)
+		.into_iter()
+		.map(|field| {
+			let mut cursor = Cursor::new(field);
+			cursor.parse_attributes();
+			cursor.skip_visibility();
+			let ident = cursor.expect_ident()?;
+			if !cursor.peek_punct(':') {
+				return Err(Error::new("expected `:` after field name"));
+			}
+			cursor.next();
+			Ok(Field {
+				ident: Some(ident),
+				ty: to_string(cursor.rest()),
+			})
+		})
+		.collect()
+}
+
+/// Parses the fields of a tuple struct or tuple variant.
+fn parse_unnamed_fields(stream: TokenStream) -> Vec<Field> {
+	let tokens = Vec::from_iter(stream);
+	split_items(&tokens)
+		.into_iter()
+		.map(|field| {
+			let mut cursor = Cursor::new(field);
+			cursor.parse_attributes();
+			cursor.skip_visibility();
+			Field {
+				ident: None,
+				ty: to_string(cursor.rest()),
+			}
+		})
+		.collect()
+}
+
+/// Parses the variants of an enum.
+fn parse_variants(stream: TokenStream) -> Result<Vec<Variant>> {
+	let tokens = Vec::from_iter(stream);
+	split_items(&tokens)
+		.into_iter()
+		.map(|variant| {
+			let mut cursor = Cursor::new(variant);
+			cursor.parse_attributes();
+			let ident = cursor.expect_ident()?;
+			let fields = match cursor.peek() {
+				Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+					Fields::Named(parse_named_fields(group.stream())?)
+				}
+				Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+					Fields::Unnamed(parse_unnamed_fields(group.stream()))
+				}
+				// Unit variants, possibly followed by an explicit discriminant.
+				_ => Fields::Unit,
+			};
+			Ok(Variant { ident, fields })
+		})
+		.collect()
+}
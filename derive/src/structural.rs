@@ -0,0 +1,84 @@
+//! Expansion of `#[tyname(structural)]`.
+
+use crate::parse::{Data, Fields, Input};
+
+/// Expands to a `StructuralTypeName` impl writing the name of the item
+/// followed by its fields and their types.
+pub fn expand(input: &Input) -> String {
+	let mut field_types = Vec::new();
+	let body = match &input.data {
+		Data::Struct(fields) => write_fields(fields, &mut field_types),
+		Data::Enum(variants) => {
+			let variants = variants
+				.iter()
+				.map(|variant| {
+					format!(
+						"::core::fmt::Write::write_str(__w, \"{}\")?; {}",
+						variant.ident,
+						write_fields(&variant.fields, &mut field_types),
+					)
+				})
+				.collect::<Vec<_>>()
+				.join(" ::core::fmt::Write::write_str(__w, \",\")?; ");
+			format!(
+				"::core::fmt::Write::write_str(__w, \"{{\")?; {} ::core::fmt::Write::write_str(__w, \"}}\")?;",
+				variants,
+			)
+		}
+	};
+	let predicates = input
+		.type_params()
+		.map(|param| param.name.clone())
+		.chain(field_types)
+		.map(|ty| format!("{}: ::tyname::TypeName", ty));
+	format!(
+		"impl{impl_generics} ::tyname::StructuralTypeName for {ident}{ty_generics} {where_clause} {{
+			fn write_structural_type_name<__TynameW>(__w: &mut __TynameW) -> ::tyname::FmtResult
+			where
+				__TynameW: ::core::fmt::Write,
+			{{
+				<Self as ::tyname::TypeName>::write_type_name(__w)?;
+				{body}
+				::core::result::Result::Ok(())
+			}}
+		}}",
+		impl_generics = input.impl_generics(),
+		ident = input.ident,
+		ty_generics = input.ty_generics(),
+		where_clause = input.where_clause(predicates),
+		body = body,
+	)
+}
+
+/// Returns statements writing the fields, e.g. `{x:f32,y:f32}` or `(f32,f32)`.
+///
+/// Collects the types of all fields into `field_types`.
+fn write_fields(fields: &Fields, field_types: &mut Vec<String>) -> String {
+	let (open, close, fields) = match fields {
+		Fields::Named(fields) => ("{", "}", fields),
+		Fields::Unnamed(fields) => ("(", ")", fields),
+		Fields::Unit => return String::new(),
+	};
+	let fields = fields
+		.iter()
+		.map(|field| {
+			field_types.push(field.ty.clone());
+			let ident = match &field.ident {
+				Some(ident) => format!(
+					"::core::fmt::Write::write_str(__w, \"{}:\")?; ",
+					ident.trim_start_matches("r#"),
+				),
+				None => String::new(),
+			};
+			format!(
+				"{}<{} as ::tyname::TypeName>::write_type_name(__w)?;",
+				ident, field.ty,
+			)
+		})
+		.collect::<Vec<_>>()
+		.join(" ::core::fmt::Write::write_str(__w, \",\")?; ");
+	format!(
+		"::core::fmt::Write::write_str(__w, \"{}\")?; {} ::core::fmt::Write::write_str(__w, \"{}\")?;",
+		open, fields, close,
+	)
+}
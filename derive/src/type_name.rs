@@ -0,0 +1,98 @@
+//! Expansion of `#[derive(TypeName)]`.
+
+use crate::parse::{GenericParamKind, Input};
+
+/// Expands to a `TypeName` impl writing the name of the item and its generic arguments.
+pub fn expand(input: &Input) -> String {
+	let skip_generics = input.options.skip_generics;
+	let predicates = input
+		.type_params()
+		.filter(|_| !skip_generics)
+		.map(|param| format!("{}: ::tyname::TypeName", param.name));
+	let (args, hints): (Vec<_>, Vec<_>) = input
+		.generics
+		.iter()
+		.filter(|_| !skip_generics)
+		.filter_map(|param| match param.kind {
+			// Lifetimes are not part of type names.
+			GenericParamKind::Lifetime => None,
+			GenericParamKind::Type => Some((
+				format!("__config.write_generic_arg::<{}, __TynameW>(__w)?;", param.name),
+				format!("<{} as ::tyname::TypeName>::type_name_len_hint()", param.name),
+			)),
+			// Note: `Debug` writes the const values as literals, e.g. quoted `char`s.
+			GenericParamKind::Const => Some((
+				format!(
+					"::core::fmt::Write::write_fmt(__w, ::core::format_args!(\"{{:?}}\", {}))?;",
+					param.name,
+				),
+				format!("::tyname::__debug_len(&{})", param.name),
+			)),
+		})
+		.unzip();
+	let arity = args.len();
+	// Note: The hint of `Ident<A, B>` adds `<`, `>` and the `, ` separators.
+	let len_hint = if hints.is_empty() {
+		format!("{}", input.ident.len())
+	} else {
+		format!("{} + {}", input.ident.len() + 2 * arity, hints.join(" + "))
+	};
+	let args = if args.is_empty() {
+		String::new()
+	} else {
+		format!(
+			"::core::fmt::Write::write_str(__w, \"<\")?; {} ::core::fmt::Write::write_str(__w, \">\")?;",
+			args.join(" __config.write_comma(__w)?; "),
+		)
+	};
+	format!(
+		"impl{impl_generics} ::tyname::TypeName for {ident}{ty_generics} {where_clause} {{
+			const MODULE_PATH: ::core::option::Option<&'static str> =
+				::core::option::Option::Some(::core::module_path!());
+			const GENERIC_ARITY: usize = {arity};
+			const NAME: ::core::option::Option<&'static str> = {name};
+
+			fn write_type_name<__TynameW>(__w: &mut __TynameW) -> ::tyname::FmtResult
+			where
+				__TynameW: ::core::fmt::Write,
+			{{
+				<Self as ::tyname::TypeName>::write_type_name_with(
+					__w,
+					&::tyname::FormatConfig::default(),
+				)
+			}}
+
+			fn write_type_name_with<__TynameW>(
+				__w: &mut __TynameW,
+				__config: &::tyname::FormatConfig,
+			) -> ::tyname::FmtResult
+			where
+				__TynameW: ::core::fmt::Write,
+			{{
+				__config.write_name(
+					__w,
+					<Self as ::tyname::TypeName>::MODULE_PATH,
+					\"{ident}\",
+				)?;
+				{args}
+				::core::result::Result::Ok(())
+			}}
+
+			fn type_name_len_hint() -> usize {{
+				{len_hint}
+			}}
+		}}",
+		impl_generics = input.impl_generics(),
+		ident = input.ident,
+		ty_generics = input.ty_generics(),
+		where_clause = input.where_clause(predicates),
+		args = args,
+		arity = arity,
+		len_hint = len_hint,
+		name = if arity == 0 {
+			format!("::core::option::Option::Some(\"{}\")", input.ident)
+		} else {
+			String::from("::core::option::Option::None")
+		},
+	)
+}
@@ -0,0 +1,144 @@
+use std::fmt::Write;
+use tyname::{type_name, TypeName};
+use tyname_derive::DebugTypeName;
+
+#[derive(DebugTypeName)]
+struct Unit;
+
+#[derive(DebugTypeName)]
+struct Wrapper<T>(T);
+
+#[derive(DebugTypeName)]
+struct Point<T> {
+	x: T,
+	y: T,
+}
+
+#[derive(DebugTypeName)]
+enum Shape {
+	Empty,
+	Circle(f32),
+	Rect { w: f32, h: f32 },
+}
+
+impl TypeName for Unit {
+	fn write_type_name<W>(w: &mut W) -> tyname::FmtResult
+	where
+		W: Write,
+	{
+		w.write_str("Unit")
+	}
+}
+
+impl<T> TypeName for Wrapper<T>
+where
+	T: TypeName,
+{
+	fn write_type_name<W>(w: &mut W) -> tyname::FmtResult
+	where
+		W: Write,
+	{
+		w.write_str("Wrapper<")?;
+		T::write_type_name(w)?;
+		w.write_str(">")
+	}
+}
+
+impl<T> TypeName for Point<T>
+where
+	T: TypeName,
+{
+	fn write_type_name<W>(w: &mut W) -> tyname::FmtResult
+	where
+		W: Write,
+	{
+		w.write_str("Point<")?;
+		T::write_type_name(w)?;
+		w.write_str(">")
+	}
+}
+
+impl TypeName for Shape {
+	fn write_type_name<W>(w: &mut W) -> tyname::FmtResult
+	where
+		W: Write,
+	{
+		w.write_str("Shape")
+	}
+}
+
+#[test]
+fn starts_with_type_name() {
+	let debug = format!("{:?}", Wrapper(42_u32));
+	assert!(debug.starts_with(&type_name::<Wrapper<u32>>()));
+	let debug = format!("{:?}", Point { x: 1_i8, y: 2_i8 });
+	assert!(debug.starts_with(&type_name::<Point<i8>>()));
+}
+
+#[test]
+fn structs() {
+	assert_eq!(format!("{:?}", Unit), "Unit");
+	assert_eq!(format!("{:?}", Wrapper(42_u32)), "Wrapper<u32>(42)");
+	assert_eq!(
+		format!("{:?}", Wrapper(Wrapper(true))),
+		"Wrapper<Wrapper<bool>>(Wrapper<bool>(true))"
+	);
+	assert_eq!(
+		format!("{:?}", Point { x: 1_i8, y: 2_i8 }),
+		"Point<i8> { x: 1, y: 2 }"
+	);
+}
+
+#[test]
+fn enums() {
+	assert_eq!(format!("{:?}", Shape::Empty), "Shape::Empty");
+	assert_eq!(format!("{:?}", Shape::Circle(1.5)), "Shape::Circle(1.5)");
+	assert_eq!(
+		format!("{:?}", Shape::Rect { w: 1.0, h: 2.0 }),
+		"Shape::Rect { w: 1.0, h: 2.0 }"
+	);
+}
+
+#[derive(tyname_derive::TypeName, DebugTypeName)]
+enum Flags {
+	A = 1 << 0,
+	B = 1 << 1,
+	C = (1 << 2) | 2 >> 1,
+}
+
+#[test]
+fn shift_discriminants() {
+	assert_eq!(type_name::<Flags>(), "Flags");
+	assert_eq!(format!("{:?}", Flags::A), "Flags::A");
+	assert_eq!(format!("{:?}", Flags::B), "Flags::B");
+	assert_eq!(format!("{:?}", Flags::C), "Flags::C");
+	assert_eq!(Flags::C as u8, 5);
+}
+
+#[derive(DebugTypeName)]
+struct Triple(u8, &'static str, char);
+
+impl TypeName for Triple {
+	fn write_type_name<W>(w: &mut W) -> tyname::FmtResult
+	where
+		W: Write,
+	{
+		w.write_str("Triple")
+	}
+}
+
+#[test]
+fn pretty() {
+	assert_eq!(format!("{:#?}", Wrapper(42_u32)), "Wrapper<u32>(\n    42,\n)");
+	assert_eq!(
+		format!("{:#?}", Point { x: 1_i8, y: 2_i8 }),
+		"Point<i8> {\n    x: 1,\n    y: 2,\n}"
+	);
+	assert_eq!(format!("{:#?}", Shape::Empty), "Shape::Empty");
+}
+
+#[test]
+fn multiple_unnamed_fields() {
+	assert_eq!(format!("{:?}", Triple(1, "a", 'b')), "Triple(1, \"a\", 'b')");
+	assert_eq!(format!("{:?}", Wrapper(Triple(1, "a", 'b'))), "Wrapper<Triple>(Triple(1, \"a\", 'b'))");
+}
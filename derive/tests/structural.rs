@@ -0,0 +1,119 @@
+#![allow(dead_code)]
+
+use tyname::{structural_signature_hash, structural_type_name};
+use tyname_derive::TypeName;
+
+#[derive(TypeName)]
+#[tyname(structural)]
+struct Point {
+	x: f32,
+	y: f32,
+}
+
+#[derive(TypeName)]
+#[tyname(structural)]
+struct Meters(i64);
+
+#[derive(TypeName)]
+#[tyname(structural)]
+struct Marker;
+
+#[derive(TypeName)]
+#[tyname(structural)]
+struct Tagged<T> {
+	tag: String,
+	value: Option<T>,
+}
+
+#[derive(TypeName)]
+#[tyname(structural)]
+enum Shape {
+	Empty,
+	Circle(f32),
+	Rect { w: f32, h: f32 },
+}
+
+#[derive(TypeName)]
+#[tyname(structural)]
+struct Node {
+	value: i32,
+	next: Option<Box<Node>>,
+}
+
+#[test]
+fn named_fields() {
+	assert_eq!(structural_type_name::<Point>(), "Point{x:f32,y:f32}");
+}
+
+#[test]
+fn tuple_and_unit() {
+	assert_eq!(structural_type_name::<Meters>(), "Meters(i64)");
+	assert_eq!(structural_type_name::<Marker>(), "Marker");
+}
+
+#[test]
+fn generic() {
+	assert_eq!(
+		structural_type_name::<Tagged<u8>>(),
+		"Tagged<u8>{tag:String,value:Option<u8>}"
+	);
+}
+
+#[test]
+fn enums() {
+	assert_eq!(
+		structural_type_name::<Shape>(),
+		"Shape{Empty,Circle(f32),Rect{w:f32,h:f32}}"
+	);
+}
+
+#[test]
+fn recursive() {
+	assert_eq!(
+		structural_type_name::<Node>(),
+		"Node{value:i32,next:Option<Box<Node>>}"
+	);
+}
+
+mod v1 {
+	#[derive(tyname_derive::TypeName)]
+	#[tyname(structural)]
+	pub struct Record {
+		pub id: u32,
+		pub name: String,
+	}
+}
+
+mod v2 {
+	#[derive(tyname_derive::TypeName)]
+	#[tyname(structural)]
+	pub struct Record {
+		pub id: u64,
+		pub name: String,
+	}
+}
+
+mod v1_copy {
+	#[derive(tyname_derive::TypeName)]
+	#[tyname(structural)]
+	pub struct Record {
+		pub id: u32,
+		pub name: String,
+	}
+}
+
+#[test]
+fn signature_hash() {
+	assert_ne!(
+		structural_signature_hash::<v1::Record>(),
+		structural_signature_hash::<v2::Record>()
+	);
+	assert_eq!(
+		structural_signature_hash::<v1::Record>(),
+		structural_signature_hash::<v1_copy::Record>()
+	);
+	assert_ne!(
+		structural_signature_hash::<Tagged<u8>>(),
+		structural_signature_hash::<Tagged<i8>>()
+	);
+}
@@ -0,0 +1,180 @@
+#![allow(dead_code)]
+
+use tyname::{module_path_of, qualified_type_name, type_name, type_name_with, FormatConfig, TypeName as _};
+use tyname_derive::TypeName;
+
+#[derive(TypeName)]
+struct Unit;
+
+#[derive(TypeName)]
+struct Wrapper<T>(T);
+
+#[derive(TypeName)]
+struct Pair<A, B> {
+	a: A,
+	b: B,
+}
+
+#[derive(TypeName)]
+struct Node {
+	value: i32,
+	next: Option<Box<Node>>,
+}
+
+#[derive(TypeName)]
+struct Tree<T> {
+	value: T,
+	children: Vec<Tree<T>>,
+}
+
+#[test]
+fn non_generic() {
+	assert_eq!(type_name::<Unit>(), "Unit");
+	assert_eq!(type_name::<Vec<Unit>>(), "Vec<Unit>");
+}
+
+#[test]
+fn generic() {
+	assert_eq!(type_name::<Wrapper<u32>>(), "Wrapper<u32>");
+	assert_eq!(type_name::<Wrapper<Wrapper<()>>>(), "Wrapper<Wrapper<()>>");
+	assert_eq!(type_name::<Pair<u8, String>>(), "Pair<u8, String>");
+}
+
+#[test]
+fn recursive() {
+	// Field types are never part of the name thus recursion is finite.
+	assert_eq!(type_name::<Node>(), "Node");
+	assert_eq!(type_name::<Option<Box<Node>>>(), "Option<Box<Node>>");
+	assert_eq!(type_name::<Tree<bool>>(), "Tree<bool>");
+}
+
+#[test]
+fn config() {
+	let config = FormatConfig {
+		paren_all_args: true,
+		..FormatConfig::default()
+	};
+	assert_eq!(
+		type_name_with::<Pair<Wrapper<u8>, Unit>>(&config),
+		"Pair<(Wrapper<(u8)>), (Unit)>"
+	);
+}
+
+mod nested {
+	#[derive(tyname_derive::TypeName)]
+	pub struct Inner;
+}
+
+#[test]
+fn module_path() {
+	assert_eq!(module_path_of::<Unit>(), Some("type_name"));
+	assert_eq!(module_path_of::<nested::Inner>(), Some("type_name::nested"));
+	assert_eq!(module_path_of::<Wrapper<Unit>>(), Some("type_name"));
+}
+
+#[test]
+fn qualified() {
+	assert_eq!(qualified_type_name::<Wrapper<Unit>>(), "type_name::Wrapper<type_name::Unit>");
+	assert_eq!(qualified_type_name::<nested::Inner>(), "type_name::nested::Inner");
+	assert_eq!(qualified_type_name::<Pair<u8, Vec<Unit>>>(), "type_name::Pair<u8, std::vec::Vec<type_name::Unit>>");
+}
+
+#[derive(TypeName)]
+#[tyname(skip_generics)]
+struct Handle<T>(T);
+
+mod plain {
+	#[derive(tyname_derive::TypeName)]
+	pub struct Handle<T>(pub T);
+}
+
+/// Does not implement `TypeName`.
+struct Opaque;
+
+#[test]
+fn skip_generics() {
+	assert_eq!(type_name::<Handle<u32>>(), "Handle");
+	assert_eq!(type_name::<Handle<Opaque>>(), "Handle");
+	assert_eq!(type_name::<Vec<Handle<String>>>(), "Vec<Handle>");
+	assert_eq!(type_name::<plain::Handle<u32>>(), "Handle<u32>");
+}
+
+#[derive(TypeName)]
+struct Timeout(std::time::Duration);
+
+#[test]
+fn std_composition() {
+	use std::time::Duration;
+
+	assert_eq!(type_name::<Timeout>(), "Timeout");
+	assert_eq!(type_name::<Wrapper<Duration>>(), "Wrapper<Duration>");
+	assert_eq!(type_name::<Wrapper<Option<Timeout>>>(), "Wrapper<Option<Timeout>>");
+}
+
+#[derive(TypeName)]
+struct Lengths<'a, T, const N: usize>(&'a [T; N]);
+
+#[test]
+fn generic_arity() {
+	assert_eq!(Unit::GENERIC_ARITY, 0);
+	assert_eq!(<Wrapper<u8>>::GENERIC_ARITY, 1);
+	assert_eq!(<Pair<u8, u8>>::GENERIC_ARITY, 2);
+	assert_eq!(<Lengths<u8, 4>>::GENERIC_ARITY, 2);
+	assert_eq!(<Handle<u8>>::GENERIC_ARITY, 0);
+}
+
+#[test]
+fn static_name() {
+	assert_eq!(tyname::static_type_name::<Unit>(), Some("Unit"));
+	assert_eq!(tyname::static_type_name::<Handle<u8>>(), Some("Handle"));
+	assert_eq!(tyname::static_type_name::<Wrapper<u8>>(), None);
+}
+
+#[derive(TypeName)]
+struct Point {
+	x: i32,
+	y: i32,
+}
+
+#[derive(TypeName)]
+enum Either<L, R> {
+	Left(L),
+	Right(R),
+}
+
+#[derive(TypeName)]
+enum Empty {}
+
+#[test]
+fn enums_and_structs() {
+	assert_eq!(type_name::<Point>(), "Point");
+	assert_eq!(type_name::<Either<i32, String>>(), "Either<i32, String>");
+	assert_eq!(type_name::<Either<Point, Unit>>(), "Either<Point, Unit>");
+	assert_eq!(type_name::<Empty>(), "Empty");
+}
+
+#[derive(TypeName)]
+struct Delimiter<const C: char>;
+
+#[derive(TypeName)]
+struct Flag<const B: bool, const N: i8>;
+
+#[test]
+fn const_literals() {
+	assert_eq!(type_name::<Delimiter<'>'>>(), "Delimiter<'>'>");
+	assert_eq!(type_name::<Vec<Delimiter<'('>>>(), "Vec<Delimiter<'('>>");
+	assert_eq!(type_name::<Delimiter<'\''>>(), r"Delimiter<'\''>");
+	assert_eq!(type_name::<Flag<true, -4>>(), "Flag<true, -4>");
+}
+
+#[test]
+fn len_hint() {
+	fn assert_len_hint<T: tyname::TypeName>() {
+		assert_eq!(T::type_name_len_hint(), type_name::<T>().len(), "length hint of {}", type_name::<T>());
+	}
+	assert_len_hint::<Unit>();
+	assert_len_hint::<Wrapper<Vec<u32>>>();
+	assert_len_hint::<Pair<u8, Tree<String>>>();
+	assert_len_hint::<Delimiter<'\''>>();
+	assert_len_hint::<Flag<true, -4>>();
+}
@@ -0,0 +1,273 @@
+//! Configuration of the written type names.
+
+use crate::{FmtResult, TypeName};
+use core::fmt::Write;
+
+/// Configures how type names are written.
+///
+/// The default configuration yields the same output as
+/// [`TypeName::write_type_name`].
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// # use tyname::{FormatConfig, type_name_with};
+/// let config = FormatConfig {
+///     paren_all_args: true,
+///     ..FormatConfig::default()
+/// };
+/// assert_eq!(
+///     type_name_with::<Vec<Result<u32, String>>>(&config),
+///     "Vec<(Result<(u32), (String)>)>"
+/// );
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct FormatConfig {
+	/// Wraps every generic argument in parentheses.
+	///
+	/// E.g. writes `Vec<(Option<(u32)>)>` instead of `Vec<Option<u32>>`.
+	/// This allows parsers without operator precedence to unambiguously
+	/// parse the written type names.
+	pub paren_all_args: bool,
+	/// Writes the smart pointers `Box`, `Rc` and `Arc` as C-style pointers.
+	///
+	/// E.g. writes `u32*` instead of `Box<u32>`.
+	pub c_pointer_smartptrs: bool,
+	/// Omits the `dyn` keyword of registered trait objects.
+	///
+	/// E.g. writes `Box<Debug>` instead of `Box<dyn Debug>` as in Rust 2015.
+	/// See [`impl_type_name_dyn!`](crate::impl_type_name_dyn) for how to
+	/// register trait objects.
+	pub legacy_dyn: bool,
+	/// Writes the unit type `()` as the word `unit`.
+	///
+	/// E.g. writes `Result<unit, String>` instead of `Result<(), String>`
+	/// for formats that do not allow empty parentheses.
+	pub unit_as_word: bool,
+	/// Writes the primitive integer and float types by their bit-widths.
+	///
+	/// E.g. writes `Vec<uint<32>>` instead of `Vec<u32>` and `int<8>`
+	/// instead of `i8` for hardware-description output.
+	pub bitwidth_primitives: bool,
+	/// Writes the types defined in a module by their full path.
+	///
+	/// E.g. writes `std::vec::Vec<u32>` instead of `Vec<u32>`. Primitives,
+	/// tuples, arrays, slices, references and pointers are not defined in
+	/// a module and thus stay unqualified.
+	/// See [`TypeName::write_qualified_type_name`] for the used paths.
+	pub qualified: bool,
+	/// Writes the type names as valid Rust type syntax.
+	///
+	/// E.g. writes `&(dyn Any + Send)` instead of `&dyn Any + Send` and the
+	/// [`Unknown`](crate::Unknown) stand-in as the inferred type `_`.
+	/// The output is only guaranteed to parse as Rust type if
+	/// [`FormatConfig::c_pointer_smartptrs`] and [`FormatConfig::legacy_dyn`]
+	/// are unset.
+	pub tokenizable: bool,
+	/// Writes the types defined in a module by the path that
+	/// `std::any::type_name` reports for them.
+	///
+	/// E.g. writes `alloc::vec::Vec<u32>` instead of `Vec<u32>`. This takes
+	/// precedence over [`FormatConfig::qualified`] and is a best-effort
+	/// mode for cross-checking against `std::any::type_name`, see
+	/// [`std_compat_type_name`](crate::std_compat_type_name) for the
+	/// known divergences.
+	pub std_compat: bool,
+	/// Writes a space after the commas separating generic arguments,
+	/// tuple elements and function parameters.
+	///
+	/// This is set by default. If unset, writes e.g. `Result<i32,String>`
+	/// instead of `Result<i32, String>` and `(u8,bool)` instead of `(u8, bool)`.
+	pub space_after_comma: bool,
+	/// Writes the lengths of arrays as hexadecimal literals.
+	///
+	/// E.g. writes `[u8; 0x400]` instead of `[u8; 1024]`.
+	pub hex_array_lens: bool,
+	/// Separates the digits of array lengths into groups by underscores.
+	///
+	/// E.g. writes `[u8; 1_024]` instead of `[u8; 1024]`. Decimal digits
+	/// are grouped by three and hexadecimal digits by four.
+	pub group_array_len_digits: bool,
+}
+
+impl Default for FormatConfig {
+	fn default() -> Self {
+		FormatConfig {
+			paren_all_args: false,
+			c_pointer_smartptrs: false,
+			legacy_dyn: false,
+			unit_as_word: false,
+			bitwidth_primitives: false,
+			qualified: false,
+			tokenizable: false,
+			std_compat: false,
+			space_after_comma: true,
+			hex_array_lens: false,
+			group_array_len_digits: false,
+		}
+	}
+}
+
+impl FormatConfig {
+	/// Writes the registered name of a trait object according to `self`.
+	///
+	/// This is used by [`impl_type_name_dyn!`](crate::impl_type_name_dyn).
+	pub fn write_dyn_name<W>(&self, w: &mut W, name: &str) -> FmtResult
+	where
+		W: Write,
+	{
+		if self.legacy_dyn {
+			w.write_str(name.strip_prefix("dyn ").unwrap_or(name))
+		} else {
+			w.write_str(name)
+		}
+	}
+
+	/// Writes the name of a type defined in `module_path` according to `self`.
+	///
+	/// The `name` is written without the generic arguments of the type.
+	/// With [`FormatConfig::qualified`] the name is prefixed by `module_path`
+	/// instead of its own module prefix, if any, e.g. `std::io::Error` is
+	/// written for `io::Error` defined in `std::io`.
+	///
+	/// Implementors of [`TypeName::write_type_name_with`] should use this
+	/// to write the name of `Self`, passing their [`TypeName::MODULE_PATH`].
+	pub fn write_name<W>(&self, w: &mut W, module_path: Option<&str>, name: &str) -> FmtResult
+	where
+		W: Write,
+	{
+		match module_path {
+			Some(path) if self.qualified || self.std_compat => {
+				let name = name.rsplit("::").next().unwrap_or(name);
+				let path = if self.std_compat {
+					defining_module_path(path, name)
+				} else {
+					path
+				};
+				w.write_str(path)?;
+				w.write_str("::")?;
+				w.write_str(name)
+			}
+			_ => w.write_str(name),
+		}
+	}
+
+	/// Writes the comma separating two generic arguments, tuple elements
+	/// or function parameters according to `self`.
+	///
+	/// Implementors of [`TypeName::write_type_name_with`] should use this
+	/// to separate their generic arguments.
+	pub fn write_comma<W>(&self, w: &mut W) -> FmtResult
+	where
+		W: Write,
+	{
+		if self.space_after_comma {
+			w.write_str(", ")
+		} else {
+			w.write_str(",")
+		}
+	}
+
+	/// Writes the length of an array according to `self`.
+	///
+	/// Implementors of [`TypeName::write_type_name_with`] should use this
+	/// to write lengths given by const generic arguments.
+	pub fn write_array_len<W>(&self, w: &mut W, len: usize) -> FmtResult
+	where
+		W: Write,
+	{
+		let (radix, group, prefix) = if self.hex_array_lens {
+			(16, 4, "0x")
+		} else {
+			(10, 3, "")
+		};
+		if !self.group_array_len_digits {
+			return if self.hex_array_lens {
+				write!(w, "{:#x}", len)
+			} else {
+				write!(w, "{}", len)
+			}
+		}
+		// The digits in reverse order, enough for the decimal `usize::MAX`.
+		let mut digits = [0_u8; 20];
+		let mut count = 0;
+		let mut rest = len;
+		loop {
+			digits[count] = b"0123456789abcdef"[rest % radix];
+			count += 1;
+			rest /= radix;
+			if rest == 0 {
+				break
+			}
+		}
+		w.write_str(prefix)?;
+		for (n, &digit) in digits[..count].iter().enumerate().rev() {
+			w.write_char(char::from(digit))?;
+			if n != 0 && n % group == 0 {
+				w.write_char('_')?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Writes the name of the generic argument `T` according to `self`.
+	///
+	/// Implementors of [`TypeName::write_type_name_with`] should use this
+	/// to write each of their generic arguments.
+	pub fn write_generic_arg<T, W>(&self, w: &mut W) -> FmtResult
+	where
+		T: TypeName + ?Sized,
+		W: Write,
+	{
+		if self.paren_all_args {
+			w.write_str("(")?;
+			T::write_type_name_with(w, self)?;
+			w.write_str(")")
+		} else {
+			T::write_type_name_with(w, self)
+		}
+	}
+}
+
+/// Returns the path of the module that actually defines the type `name`
+/// re-exported from the `std` module `module_path`.
+///
+/// These are the paths reported by `std::any::type_name` which are an
+/// implementation detail of the standard library. Types that are not
+/// listed are assumed to be defined where they are re-exported.
+fn defining_module_path<'a>(module_path: &'a str, name: &str) -> &'a str {
+	match (module_path, name) {
+		("std::boxed", _) => "alloc::boxed",
+		("std::borrow", _) => "alloc::borrow",
+		("std::rc", _) => "alloc::rc",
+		("std::string", _) => "alloc::string",
+		("std::vec", _) => "alloc::vec",
+		("std::sync", "Arc") | ("std::sync", "Weak") => "alloc::sync",
+		("std::collections", "HashMap") => "std::collections::hash::map",
+		("std::collections", "HashSet") => "std::collections::hash::set",
+		("std::collections", "BTreeMap") => "alloc::collections::btree::map",
+		("std::collections", "BTreeSet") => "alloc::collections::btree::set",
+		("std::collections", "BinaryHeap") => "alloc::collections::binary_heap",
+		("std::collections", "LinkedList") => "alloc::collections::linked_list",
+		("std::collections", "VecDeque") => "alloc::collections::vec_deque",
+		("std::cell", _) => "core::cell",
+		("std::cmp", _) => "core::cmp",
+		("std::convert", _) => "core::convert",
+		("std::fmt", _) => "core::fmt",
+		("std::io", "Error") => "std::io::error",
+		("std::marker", _) => "core::marker",
+		("std::option", _) => "core::option",
+		("std::pin", _) => "core::pin",
+		("std::result", _) => "core::result",
+		("std::time", "Duration") => "core::time",
+		("std::num", "NonZero") => "core::num::nonzero",
+		("std::num", "Wrapping") => "core::num::wrapping",
+		("std::num", "Saturating") => "core::num::saturating",
+		("std::num", "ParseIntError") => "core::num::error",
+		("std::num", "ParseFloatError") => "core::num::dec2flt",
+		("std::str", "Utf8Error") => "core::str::error",
+		_ => module_path,
+	}
+}
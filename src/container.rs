@@ -0,0 +1,69 @@
+//! Naming the element types of containers.
+
+use crate::{type_name, TypeName};
+use alloc::{
+	boxed::Box,
+	collections::{LinkedList, VecDeque},
+	rc::Rc,
+	string::String,
+	sync::Arc,
+	vec::Vec,
+};
+
+/// Containers that can name the type of their elements.
+///
+/// Useful in generic container code to log the element type.
+///
+/// # Example
+///
+/// ```
+/// # use tyname::ContainerTypeName;
+/// fn describe<C>(_container: &C) -> String
+/// where
+///     C: ContainerTypeName,
+/// {
+///     format!("container of {}", C::element_type_name())
+/// }
+///
+/// assert_eq!(describe(&vec![1_u32, 2, 3]), "container of u32");
+/// ```
+pub trait ContainerTypeName {
+	/// The type of the elements of the container.
+	type Element: TypeName + ?Sized;
+
+	/// Returns the name of the element type of the container.
+	fn element_type_name() -> String {
+		type_name::<Self::Element>()
+	}
+}
+
+macro_rules! impl_container_type_name {
+	( $( $ty:ty $(, ?$sized:ident)? );* $(;)? ) => {
+		$(
+			impl<T> ContainerTypeName for $ty
+			where
+				T: TypeName $(+ ?$sized)?
+			{
+				type Element = T;
+			}
+		)*
+	}
+}
+
+impl_container_type_name!(
+	Box<T>, ?Sized;
+	Rc<T>, ?Sized;
+	Arc<T>, ?Sized;
+	Option<T>;
+	Vec<T>;
+	VecDeque<T>;
+	LinkedList<T>;
+	[T];
+);
+
+impl<T, const N: usize> ContainerTypeName for [T; N]
+where
+	T: TypeName,
+{
+	type Element = T;
+}
@@ -0,0 +1,94 @@
+//! GraphQL type names.
+
+use crate::{render, FmtResult};
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+
+/// Types that can write their name as GraphQL type.
+///
+/// Rust primitives map to the GraphQL scalar types, e.g. `i32` to `Int`
+/// and `bool` to `Boolean`, while `Vec<T>` maps to the list type `[T]`.
+///
+/// GraphQL types are nullable by default so every type except
+/// `Option<T>` is written as non-null type, e.g. `i32` as `Int!`
+/// and `Option<i32>` as `Int`.
+pub trait GraphQLTypeName {
+	/// Whether `Self` is a nullable type.
+	const NULLABLE: bool = false;
+
+	/// Writes the GraphQL name of `Self` without the non-null marker `!`.
+	fn write_graphql_nullable_type_name<W>(writer: &mut W) -> FmtResult
+	where
+		W: Write;
+
+	/// Writes the GraphQL name of `Self`.
+	///
+	/// This is followed by the non-null marker `!` unless `Self` is nullable.
+	fn write_graphql_type_name<W>(writer: &mut W) -> FmtResult
+	where
+		W: Write,
+	{
+		Self::write_graphql_nullable_type_name(writer)?;
+		if !Self::NULLABLE {
+			writer.write_str("!")?;
+		}
+		Ok(())
+	}
+}
+
+/// Returns the GraphQL name of the given type.
+pub fn graphql_type_name<T>() -> String
+where
+	T: GraphQLTypeName + ?Sized
+{
+	render("graphql_type_name", |mut w| T::write_graphql_type_name(&mut w))
+}
+
+macro_rules! impl_graphql_scalar {
+	( $( $ty:ty => $repr:expr ),* $(,)? ) => {
+		$(
+			impl GraphQLTypeName for $ty {
+				fn write_graphql_nullable_type_name<W>(w: &mut W) -> FmtResult where W: Write {
+					w.write_str($repr)
+				}
+			}
+		)*
+	}
+}
+
+// Note: GraphQL `Int` is a signed 32-bit integer, so only the
+//       integer types that losslessly fit into it are mapped.
+impl_graphql_scalar!(
+	bool => "Boolean",
+	u8 => "Int",
+	u16 => "Int",
+	i8 => "Int",
+	i16 => "Int",
+	i32 => "Int",
+	f32 => "Float",
+	f64 => "Float",
+	str => "String",
+	String => "String",
+);
+
+impl<T> GraphQLTypeName for Option<T>
+where
+	T: GraphQLTypeName
+{
+	const NULLABLE: bool = true;
+
+	fn write_graphql_nullable_type_name<W>(w: &mut W) -> FmtResult where W: Write {
+		T::write_graphql_nullable_type_name(w)
+	}
+}
+
+impl<T> GraphQLTypeName for Vec<T>
+where
+	T: GraphQLTypeName
+{
+	fn write_graphql_nullable_type_name<W>(w: &mut W) -> FmtResult where W: Write {
+		w.write_str("[")?;
+		T::write_graphql_type_name(w)?;
+		w.write_str("]")
+	}
+}
@@ -0,0 +1,61 @@
+//! Deterministic hashing of written names.
+
+use crate::{FmtResult, TypeName};
+use core::fmt::Write;
+
+/// Returns a stable hash of the name of the given type.
+///
+/// The hash is the 64-bit FNV-1a hash of the name written by
+/// [`TypeName::write_type_name`] and thus is the same across runs,
+/// platforms and versions of this crate as long as the name is.
+/// This makes it usable for type tags in serialized data.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// # use tyname::type_name_hash;
+/// assert_eq!(type_name_hash::<Vec<u8>>(), type_name_hash::<Vec<u8>>());
+/// assert_ne!(type_name_hash::<Vec<u8>>(), type_name_hash::<Vec<i8>>());
+/// # }
+/// ```
+pub fn type_name_hash<T>() -> u64
+where
+	T: TypeName + ?Sized
+{
+	let mut hasher = Fnv1aWriter::new();
+	T::write_type_name(&mut hasher)
+		.expect("[tyname::type_name_hash] Encountered error while hashing type name");
+	hasher.finish()
+}
+
+/// A writer that hashes the bytes written to it using 64-bit FNV-1a.
+///
+/// Unlike the hashers of the standard library FNV-1a is fully specified
+/// and thus yields the same hash across runs, platforms and versions.
+pub(crate) struct Fnv1aWriter(u64);
+
+impl Fnv1aWriter {
+	const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+	const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+	/// Creates a new writer with the initial FNV-1a state.
+	pub fn new() -> Self {
+		Fnv1aWriter(Self::OFFSET_BASIS)
+	}
+
+	/// Returns the hash of all bytes written so far.
+	pub fn finish(&self) -> u64 {
+		self.0
+	}
+}
+
+impl Write for Fnv1aWriter {
+	fn write_str(&mut self, s: &str) -> FmtResult {
+		for byte in s.bytes() {
+			self.0 ^= u64::from(byte);
+			self.0 = self.0.wrapping_mul(Self::PRIME);
+		}
+		Ok(())
+	}
+}
@@ -1,39 +1,1539 @@
 //! Retrieve type names during program execution on **stable** Rust.
+//!
+//! # Features
+//!
+//! - `std` (default): Implements `TypeName` for the types of the standard
+//!   library that are not available in `core` and `alloc`, e.g. `HashMap`.
+//!   Enables `alloc`.
+//! - `alloc`: Implements `TypeName` for the types of `alloc`, e.g. `Vec`,
+//!   and enables all functions returning a `String`, e.g. [`type_name`].
+//! - `derive`: Re-exports the derive macros of `tyname-derive`.
+//! - `allocator_api`: Implements `TypeName` for `Box`, `Vec`, etc. with
+//!   any allocator instead of only the default one. Requires nightly Rust.
+//!
+//! Without the `std` feature the crate is `no_std` and the trait and its
+//! `core` impls only depend on [`core::fmt::Write`].
 
 #![doc(html_root_url = "https://docs.rs/crate/tyname/0.1.0")]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 
-#[cfg(test)]
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(all(test, feature = "std"))]
 mod tests;
+mod config;
+#[cfg(feature = "alloc")]
+mod container;
+#[cfg(feature = "alloc")]
+mod graphql;
+mod hash;
+#[cfg(feature = "alloc")]
+mod proto;
+mod structural;
+mod tokens;
+#[cfg(feature = "alloc")]
+mod tree;
+#[cfg(feature = "alloc")]
+mod visit;
+
+pub use self::{
+	config::FormatConfig,
+	hash::type_name_hash,
+	structural::{StructuralTypeName, structural_signature_hash},
+	tokens::{Token, Tokens},
+};
+
+#[cfg(feature = "alloc")]
+pub use self::{
+	container::ContainerTypeName,
+	graphql::{GraphQLTypeName, graphql_type_name},
+	proto::{ProtoTypeName, proto_type_name},
+	structural::structural_type_name,
+	tokens::{IntoTokens, OwnedToken, type_name_tokens, type_name_tokens_into},
+	tree::TypeNameTree,
+	visit::{TypeNameVisitor, visit_type_name},
+};
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
+use core::fmt::Write;
+use core::marker::PhantomData;
 
-use std::fmt::Write;
+/// Derives `TypeName` writing the name of the type followed by its generic arguments.
+#[cfg(feature = "derive")]
+pub use tyname_derive::TypeName;
+
+/// Derives `Debug` printing the type name of `Self` followed by its fields.
+#[cfg(feature = "derive")]
+pub use tyname_derive::DebugTypeName;
 
 /// The result type for this crate.
-pub type Result = std::fmt::Result;
+///
+/// Named `FmtResult` so that it does not clash with the prelude's `Result`.
+/// Note though that until the deprecated [`Result`] alias is removed a glob
+/// import of this crate still shadows the prelude's `Result` and every use
+/// of it emits a deprecation warning. Import the used items explicitly to
+/// avoid this.
+///
+/// # Example
+///
+/// ```
+/// #![deny(deprecated)]
+/// # #[cfg(feature = "alloc")] {
+/// use tyname::type_name;
+///
+/// fn parse(s: &str) -> Result<u32, String> {
+///     s.parse().map_err(|_| type_name::<u32>())
+/// }
+///
+/// assert_eq!(parse("42"), Ok(42));
+/// assert_eq!(parse("x"), Err(String::from("u32")));
+/// # }
+/// ```
+pub type FmtResult = core::fmt::Result;
+
+/// The former name of [`FmtResult`].
+///
+/// The generic parameters default to those of [`FmtResult`] so that
+/// both `-> Result` and user code written against `core::result::Result`
+/// keep compiling with a glob import of this crate.
+///
+/// Since a glob import of this crate shadows the prelude's `Result` with
+/// this alias such code emits deprecation warnings:
+///
+/// ```compile_fail
+/// #![deny(deprecated)]
+/// use tyname::*;
+///
+/// fn parse(s: &str) -> Result<u32, String> {
+///     s.parse().map_err(|_| type_name::<u32>())
+/// }
+/// # parse("42").unwrap();
+/// ```
+#[deprecated(note = "use `FmtResult` instead")]
+pub type Result<T = (), E = core::fmt::Error> = core::result::Result<T, E>;
 
 /// Types that implement this trait can write their name.
+///
+/// # Defaulted generic arguments
+///
+/// Generic arguments that have a default and are usually left out by
+/// users are elided, so that names read the way the types are written:
+///
+/// - The hasher `S` of `HashMap<K, V, S>` and `HashSet<T, S>` is never written.
+/// - The allocator `A` of `Box`, `Vec`, etc. is never written since only
+///   the default allocator can be named on stable Rust.
+/// - The continue type `C` of `ControlFlow<B, C>` is elided if it is `()`.
+///
+/// # Unsupported types
+///
+/// Types that do not implement this trait cannot be named, neither on
+/// their own nor as generic argument of a supported type.
+///
+/// ```compile_fail,E0277
+/// # use tyname::type_name;
+/// struct Foo;
+///
+/// type_name::<Foo>();
+/// ```
+///
+/// ```compile_fail,E0277
+/// # use tyname::type_name;
+/// # use std::collections::HashMap;
+/// struct Foo;
+///
+/// type_name::<HashMap<Foo, u32>>();
+/// ```
+///
+/// Implement it with `#[derive(TypeName)]`, [`impl_type_name!`] or by hand.
+#[diagnostic::on_unimplemented(
+	message = "`{Self}` does not implement `TypeName`",
+	label = "cannot name this type",
+	note = "derive it with `#[derive(TypeName)]` or implement it with `tyname::impl_type_name!`"
+)]
 pub trait TypeName {
-	/// Applies the keccak hash of `self` for the given keccak hasher.
-	fn write_type_name<W>(writer: &mut W) -> Result
+	/// The path of the module that defines `Self`, e.g. `std::boxed` for `Box<T>`.
+	///
+	/// This is `None` for types that do not live in a module,
+	/// e.g. primitives, tuples, arrays, slices, references and pointers.
+	const MODULE_PATH: Option<&'static str> = None;
+
+	/// Whether `Self` is the unit type `()`.
+	///
+	/// Used to elide defaulted `()` generic arguments.
+	#[doc(hidden)]
+	const IS_UNIT: bool = false;
+
+	/// Whether `Self` is a trait object with additional bounds, e.g. `dyn Any + Send`.
+	///
+	/// Used to parenthesize such trait objects behind references and
+	/// pointers with [`FormatConfig::tokenizable`].
+	#[doc(hidden)]
+	const IS_DYN_SUM: bool = false;
+
+	/// The number of generic arguments written for `Self`.
+	///
+	/// E.g. this is `0` for `u32`, `1` for `Vec<u32>`, `2` for
+	/// `Result<u32, String>` and `3` for `(u8, u8, u8)`. Elided defaulted
+	/// arguments such as the hasher of a `HashMap` are not counted.
+	/// References, pointers, arrays, slices and function pointers have
+	/// no generic arguments in this sense.
+	const GENERIC_ARITY: usize = 0;
+
+	/// The statically known name of `Self`, if any.
+	///
+	/// This is set for types without generic arguments, e.g. `Some("u32")`
+	/// for `u32`, and `None` for all other types, e.g. `Vec<u32>`.
+	/// It equals the name written by [`TypeName::write_type_name`], i.e.
+	/// the name with the default [`FormatConfig`].
+	const NAME: Option<&'static str> = None;
+
+	/// Writes the name of `Self` into the given writer.
+	fn write_type_name<W>(writer: &mut W) -> FmtResult
 	where
 		W: Write;
+
+	/// Writes the name of `Self` according to the given configuration.
+	///
+	/// The default implementation ignores the configuration and forwards
+	/// to [`TypeName::write_type_name`]. Types with generic arguments should
+	/// override it and write their arguments using
+	/// [`FormatConfig::write_generic_arg`] so that the configuration
+	/// applies to them as well.
+	fn write_type_name_with<W>(writer: &mut W, config: &FormatConfig) -> FmtResult
+	where
+		W: Write,
+	{
+		let _ = config;
+		Self::write_type_name(writer)
+	}
+
+	/// Returns the expected length in bytes of the name of `Self`.
+	///
+	/// Used to pre-allocate the buffer of [`type_name`] without writing
+	/// the name twice. Leaves return the length of their name and generic
+	/// types add the hints of their generic arguments to the length of
+	/// their name and punctuation, so the hints of this crate are exact.
+	///
+	/// The default implementation returns the length of [`TypeName::NAME`]
+	/// if set and otherwise `0`, in which case the buffer grows as needed.
+	/// Types with generic arguments should override it.
+	fn type_name_len_hint() -> usize {
+		match Self::NAME {
+			Some(name) => name.len(),
+			None => 0,
+		}
+	}
+
+	/// Writes the name of `Self` with all of its types qualified by their module path.
+	///
+	/// E.g. writes `std::boxed::Box<std::vec::Vec<i32>>` for `Box<Vec<i32>>`.
+	/// Types that are not defined in a module, such as primitives, are
+	/// written unqualified.
+	///
+	/// # Note
+	///
+	/// The types of `core` and `alloc` are re-exported by `std` and are
+	/// always qualified by their `std` path, e.g. `std::vec::Vec` instead
+	/// of `alloc::vec::Vec`, even without the `std` crate feature.
+	///
+	/// The default implementation forwards to [`TypeName::write_type_name_with`]
+	/// with [`FormatConfig::qualified`] set. Types that do not write
+	/// themselves using [`FormatConfig::write_name`] are written by
+	/// their short name.
+	fn write_qualified_type_name<W>(writer: &mut W) -> FmtResult
+	where
+		W: Write,
+	{
+		let config = FormatConfig {
+			qualified: true,
+			..FormatConfig::default()
+		};
+		Self::write_type_name_with(writer, &config)
+	}
 }
 
 /// Returns the name of the given type.
+///
+/// The buffer of the name is pre-allocated with the capacity given by
+/// [`TypeName::type_name_len_hint`], so that writing the name of types
+/// with exact hints never has to reallocate.
+///
+/// # Panics
+///
+/// With debug assertions enabled, if the written name has unbalanced
+/// `<>`, `()` or `[]` brackets which hints at a broken [`TypeName`] impl.
+#[cfg(feature = "alloc")]
 pub fn type_name<T>() -> String
 where
 	T: TypeName + ?Sized
 {
-	let mut buffer = String::new();
-	T::write_type_name(&mut buffer)
+	let buffer = try_type_name::<T>()
 		.expect("[tyname::type_name] Encountered error while writing type name");
+	#[cfg(debug_assertions)]
+	assert_balanced_brackets(&buffer);
 	buffer
 }
 
+/// Returns the name of the given type or the error of its [`TypeName`] impl.
+///
+/// Unlike [`type_name`] this never panics.
+///
+/// # Errors
+///
+/// If the [`TypeName`] impl of `T` or of any of its generic arguments
+/// returns an error while writing the name.
+#[cfg(feature = "alloc")]
+pub fn try_type_name<T>() -> core::result::Result<String, core::fmt::Error>
+where
+	T: TypeName + ?Sized
+{
+	let mut buffer = String::with_capacity(T::type_name_len_hint());
+	T::write_type_name(&mut buffer)?;
+	Ok(buffer)
+}
+
+/// Returns the name of the type of the given value.
+///
+/// This is equal to `type_name::<T>()` but infers `T` from the value,
+/// mirroring `std::any::type_name_of_val`.
+///
+/// # Example
+///
+/// ```
+/// # use tyname::type_name_of_val;
+/// let values = vec![1_u8, 2, 3];
+/// assert_eq!(type_name_of_val(&values), "Vec<u8>");
+/// assert_eq!(type_name_of_val(&values[..]), "[u8]");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn type_name_of_val<T>(_val: &T) -> String
+where
+	T: TypeName + ?Sized
+{
+	type_name::<T>()
+}
+
+/// Returns the name of the given type, borrowing it if statically known.
+///
+/// This is equal to `type_name::<T>()` but does not allocate for types
+/// with a [`TypeName::NAME`], e.g. primitives and types without generic
+/// arguments.
+///
+/// # Example
+///
+/// ```
+/// # use tyname::type_name_cow;
+/// # use std::borrow::Cow;
+/// assert_eq!(type_name_cow::<u32>(), Cow::Borrowed("u32"));
+/// assert_eq!(type_name_cow::<Vec<u32>>(), Cow::<str>::Owned(String::from("Vec<u32>")));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn type_name_cow<T>() -> Cow<'static, str>
+where
+	T: TypeName + ?Sized
+{
+	match T::NAME {
+		Some(name) => Cow::Borrowed(name),
+		None => Cow::Owned(type_name::<T>()),
+	}
+}
+
+/// Asserts that all `<>`, `()` and `[]` brackets of the name are balanced.
+///
+/// The `>` of a function's return arrow `->` is not a bracket and neither
+/// are brackets within `char` literals, e.g. of the const generic `C<'>'>`.
+#[cfg(all(feature = "alloc", debug_assertions))]
+fn assert_balanced_brackets(name: &str) {
+	let mut open = alloc::vec::Vec::new();
+	let mut prev = None;
+	let mut chars = name.chars();
+	while let Some(c) = chars.next() {
+		match c {
+			'\'' => {
+				// Note: A quote not closed after one (escaped) char starts a lifetime.
+				let mut ahead = chars.clone();
+				match (ahead.next(), ahead.next()) {
+					(Some('\\'), Some(_)) if ahead.any(|c| c == '\'') => chars = ahead,
+					(Some(_), Some('\'')) => chars = ahead,
+					_ => (),
+				}
+			}
+			'<' => open.push('>'),
+			'(' => open.push(')'),
+			'[' => open.push(']'),
+			'>' if prev == Some('-') => (),
+			'>' | ')' | ']' => {
+				assert_eq!(
+					open.pop(), Some(c),
+					"[tyname::type_name] Encountered unbalanced brackets in type name `{}`", name
+				)
+			}
+			_ => (),
+		}
+		prev = Some(c);
+	}
+	assert!(
+		open.is_empty(),
+		"[tyname::type_name] Encountered unbalanced brackets in type name `{}`", name
+	);
+}
+
+/// Writes a name into a new buffer with the capacity counted in a first pass.
+///
+/// # Panics
+///
+/// If `write` returns an error, naming the public function `caller`.
+#[cfg(feature = "alloc")]
+pub(crate) fn render<F>(caller: &str, write: F) -> String
+where
+	F: Fn(&mut dyn Write) -> FmtResult,
+{
+	let mut counter = ByteCountWriter(0);
+	let mut buffer = match write(&mut counter) {
+		Ok(()) => String::with_capacity(counter.0),
+		Err(_) => String::new(),
+	};
+	if write(&mut buffer).is_err() {
+		panic!("[tyname::{}] Encountered error while writing type name", caller)
+	}
+	buffer
+}
+
+/// Returns the name of the given type written according to the configuration.
+#[cfg(feature = "alloc")]
+pub fn type_name_with<T>(config: &FormatConfig) -> String
+where
+	T: TypeName + ?Sized
+{
+	render("type_name_with", |mut w| T::write_type_name_with(&mut w, config))
+}
+
+/// Returns the name of the given type with primitive numbers written by bit-width.
+///
+/// This is a shorthand for [`type_name_with`] with
+/// [`FormatConfig::bitwidth_primitives`] set, e.g. `Vec<u32>`
+/// is written as `Vec<uint<32>>` and `f64` as `float<64>`.
+#[cfg(feature = "alloc")]
+pub fn bitwidth_type_name<T>() -> String
+where
+	T: TypeName + ?Sized
+{
+	type_name_with::<T>(&FormatConfig {
+		bitwidth_primitives: true,
+		..FormatConfig::default()
+	})
+}
+
+/// Returns the name of the given type with all of its types qualified by their module path.
+///
+/// See [`TypeName::write_qualified_type_name`] for the written paths.
+///
+/// # Example
+///
+/// ```
+/// # use tyname::qualified_type_name;
+/// assert_eq!(qualified_type_name::<Option<bool>>(), "std::option::Option<bool>");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn qualified_type_name<T>() -> String
+where
+	T: TypeName + ?Sized
+{
+	render("qualified_type_name", |mut w| T::write_qualified_type_name(&mut w))
+}
+
+/// Returns the name of the given type the way `std::any::type_name` writes it.
+///
+/// This is a shorthand for [`type_name_with`] with
+/// [`FormatConfig::std_compat`] set, e.g. `Vec<i32>` is written
+/// as `alloc::vec::Vec<i32>` and `Option<u8>` as `core::option::Option<u8>`.
+///
+/// # Note
+///
+/// The output of `std::any::type_name` is not guaranteed to be stable,
+/// so this is a best-effort mode for debugging. Known divergences are:
+///
+/// - Lifetimes are never written, e.g. `alloc::borrow::Cow<str>` instead
+///   of ``alloc::borrow::Cow<'_, str>``.
+/// - Registered trait objects are written by their registered names,
+///   e.g. `dyn Any` instead of `dyn core::any::Any`.
+/// - Types that are defined in private modules of the standard library
+///   which are not listed are written by their public `std` path, e.g.
+///   `std::sync::Mutex` instead of `std::sync::poison::mutex::Mutex`.
+#[cfg(feature = "alloc")]
+pub fn std_compat_type_name<T>() -> String
+where
+	T: TypeName + ?Sized
+{
+	type_name_with::<T>(&FormatConfig {
+		std_compat: true,
+		..FormatConfig::default()
+	})
+}
+
+/// Returns the name of the given type as valid Rust type syntax.
+///
+/// The returned name is guaranteed to tokenize and parse as a Rust type,
+/// e.g. as a `syn::Type`, so that procedural macros can splice it back
+/// into generated code. This is a shorthand for [`type_name_with`] with
+/// [`FormatConfig::tokenizable`] set.
+///
+/// # Example
+///
+/// ```
+/// # use tyname::tokenizable_type_name;
+/// use std::any::Any;
+///
+/// assert_eq!(tokenizable_type_name::<&(dyn Any + Send)>(), "&(dyn Any + Send)");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn tokenizable_type_name<T>() -> String
+where
+	T: TypeName + ?Sized
+{
+	type_name_with::<T>(&FormatConfig {
+		tokenizable: true,
+		..FormatConfig::default()
+	})
+}
+
+/// Writes the name of the given type into `buffer` and returns it.
+///
+/// The previous contents of `buffer` are cleared while its allocation is
+/// reused, so naming many types into the same buffer allocates only when
+/// a name outgrows the capacity. The returned name borrows `buffer` and
+/// thus has to be dropped before the buffer is used again.
+///
+/// # Example
+///
+/// ```
+/// # use tyname::type_name_reuse;
+/// let mut buffer = String::new();
+/// assert_eq!(type_name_reuse::<u32>(&mut buffer), "u32");
+/// assert_eq!(type_name_reuse::<Vec<u8>>(&mut buffer), "Vec<u8>");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn type_name_reuse<T>(buffer: &mut String) -> &str
+where
+	T: TypeName + ?Sized
+{
+	buffer.clear();
+	T::write_type_name(buffer)
+		.expect("[tyname::type_name_reuse] Encountered error while writing type name");
+	buffer
+}
+
+/// Calls `f` with the name of the given type written into a thread-local buffer.
+///
+/// This is equal to `f(&type_name::<T>())` but reuses the allocation of
+/// the buffer across calls on the same thread. Nested calls from within
+/// `f` fall back to a fresh buffer.
+///
+/// # Example
+///
+/// ```
+/// # use tyname::with_type_name;
+/// let len = with_type_name::<Vec<u8>, _>(|name| name.len());
+/// assert_eq!(len, "Vec<u8>".len());
+/// ```
+#[cfg(feature = "std")]
+pub fn with_type_name<T, R>(f: impl FnOnce(&str) -> R) -> R
+where
+	T: TypeName + ?Sized
+{
+	std::thread_local! {
+		static BUFFER: core::cell::RefCell<String> = const { core::cell::RefCell::new(String::new()) };
+	}
+	BUFFER.with(|buffer| match buffer.try_borrow_mut() {
+		Ok(mut buffer) => f(type_name_reuse::<T>(&mut buffer)),
+		Err(_) => f(&type_name::<T>()),
+	})
+}
+
+/// Appends the name of the given type to `buffer`.
+///
+/// Unlike [`type_name_reuse`] the previous contents of `buffer` are kept,
+/// which allows to build lists of type names in a single buffer.
+///
+/// # Example
+///
+/// ```
+/// # use tyname::type_name_into;
+/// let mut buffer = String::from("(");
+/// type_name_into::<u32>(&mut buffer);
+/// buffer.push_str(", ");
+/// type_name_into::<Vec<u8>>(&mut buffer);
+/// buffer.push(')');
+/// assert_eq!(buffer, "(u32, Vec<u8>)");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn type_name_into<T>(buffer: &mut String)
+where
+	T: TypeName + ?Sized
+{
+	buffer.reserve(type_name_len::<T>());
+	T::write_type_name(buffer)
+		.expect("[tyname::type_name_into] Encountered error while writing type name");
+}
+
+/// Returns the name of the given type if it fits into `max_bytes` bytes.
+///
+/// Writing is aborted as soon as the name exceeds the budget and the
+/// buffer never grows beyond it so that pathologically long names never
+/// reserve more than `max_bytes` bytes.
+///
+/// # Errors
+///
+/// If the name of the type is longer than `max_bytes` bytes.
+///
+/// # Panics
+///
+/// If the [`TypeName`] impl of the type returns an error of its own.
+#[cfg(feature = "alloc")]
+pub fn type_name_bounded<T>(max_bytes: usize) -> core::result::Result<String, SizeExceeded>
+where
+	T: TypeName + ?Sized
+{
+	let mut writer = BoundedWriter {
+		buffer: String::new(),
+		max_bytes,
+		exceeded: false,
+	};
+	let result = T::write_type_name(&mut writer);
+	if writer.exceeded {
+		return Err(SizeExceeded { max_bytes })
+	}
+	result.expect("[tyname::type_name_bounded] Encountered error while writing type name");
+	Ok(writer.buffer)
+}
+
+/// Error returned by [`type_name_bounded`] if a name exceeds its byte budget.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SizeExceeded {
+	/// The exceeded budget in bytes.
+	pub max_bytes: usize,
+}
+
+impl core::fmt::Display for SizeExceeded {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> FmtResult {
+		write!(f, "type name exceeds the budget of {} bytes", self.max_bytes)
+	}
+}
+
+impl core::error::Error for SizeExceeded {}
+
+/// A writer that errors instead of growing beyond its byte budget.
+#[cfg(feature = "alloc")]
+struct BoundedWriter {
+	buffer: String,
+	max_bytes: usize,
+	/// Whether a write has been rejected for exceeding the budget.
+	exceeded: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl Write for BoundedWriter {
+	fn write_str(&mut self, s: &str) -> FmtResult {
+		let len = self.buffer.len() + s.len();
+		if len > self.max_bytes {
+			self.exceeded = true;
+			return Err(core::fmt::Error)
+		}
+		if len > self.buffer.capacity() {
+			// Note: Grows by doubling like `String` but never beyond the budget.
+			let capacity = core::cmp::min(core::cmp::max(len, 2 * self.buffer.capacity()), self.max_bytes);
+			self.buffer.reserve_exact(capacity - self.buffer.len());
+		}
+		self.buffer.push_str(s);
+		Ok(())
+	}
+}
+
+/// Returns the name of the given type up to the given generic nesting depth.
+///
+/// The generic arguments nested deeper than `max_depth` are replaced by `…`.
+/// A depth of `0` elides the whole name.
+///
+/// # Example
+///
+/// ```
+/// # use tyname::type_name_truncated;
+/// # use std::collections::BTreeMap;
+/// type Nested = Vec<BTreeMap<String, Vec<u8>>>;
+///
+/// assert_eq!(type_name_truncated::<Nested>(0), "…");
+/// assert_eq!(type_name_truncated::<Nested>(1), "Vec<…>");
+/// assert_eq!(type_name_truncated::<Nested>(2), "Vec<BTreeMap<…>>");
+/// assert_eq!(type_name_truncated::<Nested>(3), "Vec<BTreeMap<String, Vec<…>>>");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn type_name_truncated<T>(max_depth: usize) -> String
+where
+	T: TypeName + ?Sized
+{
+	if max_depth == 0 {
+		return String::from("…")
+	}
+	let mut writer = DepthLimitedWriter {
+		buffer: String::new(),
+		max_depth,
+		depth: 0,
+		last: '\0',
+	};
+	T::write_type_name(&mut writer)
+		.expect("[tyname::type_name_truncated] Encountered error while writing type name");
+	writer.buffer
+}
+
+/// A writer that elides the generic arguments nested deeper than `max_depth`.
+#[cfg(feature = "alloc")]
+struct DepthLimitedWriter {
+	buffer: String,
+	max_depth: usize,
+	/// The number of currently open `<`.
+	depth: usize,
+	/// The last written character, used to tell the `>` of `->` apart.
+	last: char,
+}
+
+#[cfg(feature = "alloc")]
+impl Write for DepthLimitedWriter {
+	fn write_str(&mut self, s: &str) -> FmtResult {
+		for c in s.chars() {
+			let visible = self.depth < self.max_depth;
+			match c {
+				'<' => {
+					if visible {
+						self.buffer.push('<');
+					}
+					self.depth += 1;
+					if self.depth == self.max_depth {
+						self.buffer.push('…');
+					}
+				}
+				// Note: The `>` of the `->` of function pointers closes nothing.
+				'>' if self.last != '-' => {
+					self.depth = self.depth.saturating_sub(1);
+					if self.depth < self.max_depth {
+						self.buffer.push('>');
+					}
+				}
+				c if visible => self.buffer.push(c),
+				_ => (),
+			}
+			self.last = c;
+		}
+		Ok(())
+	}
+}
+
+/// Returns the name of the given type with its generic arguments on separate lines.
+///
+/// Every generic argument is written on its own line, indented by four
+/// spaces per nesting level, and the closing `>` is dedented again.
+/// Types without generic arguments stay on a single line, as do the
+/// elements of tuples and arrays.
+///
+/// # Example
+///
+/// ```
+/// # use tyname::type_name_pretty;
+/// assert_eq!(type_name_pretty::<u32>(), "u32");
+/// assert_eq!(
+///     type_name_pretty::<Result<Vec<u8>, String>>(),
+///     "Result<\n    Vec<\n        u8\n    >,\n    String\n>"
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn type_name_pretty<T>() -> String
+where
+	T: TypeName + ?Sized
+{
+	let mut writer = PrettyWriter {
+		buffer: String::new(),
+		brackets: alloc::vec![0],
+		skip_space: false,
+		last: '\0',
+	};
+	T::write_type_name(&mut writer)
+		.expect("[tyname::type_name_pretty] Encountered error while writing type name");
+	writer.buffer
+}
+
+/// A writer that breaks the generic arguments of type names into lines.
+#[cfg(feature = "alloc")]
+struct PrettyWriter {
+	buffer: String,
+	/// The number of open `()` and `[]` brackets per open `<`.
+	///
+	/// Only the commas outside of these brackets separate generic arguments.
+	brackets: alloc::vec::Vec<usize>,
+	/// Whether the space following a separating comma is to be skipped.
+	skip_space: bool,
+	/// The last written character, used to tell the `>` of `->` apart.
+	last: char,
+}
+
+#[cfg(feature = "alloc")]
+impl PrettyWriter {
+	/// Starts a new line indented by the current nesting level.
+	fn new_line(&mut self) {
+		self.buffer.push('\n');
+		for _ in 1..self.brackets.len() {
+			self.buffer.push_str("    ");
+		}
+	}
+
+	/// Returns the number of open `()` and `[]` within the innermost `<`.
+	fn open_brackets(&mut self) -> &mut usize {
+		self.brackets.last_mut().expect("the outermost level is never closed")
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl Write for PrettyWriter {
+	fn write_str(&mut self, s: &str) -> FmtResult {
+		for c in s.chars() {
+			let skip_space = core::mem::replace(&mut self.skip_space, false);
+			match c {
+				'<' => {
+					self.buffer.push('<');
+					self.brackets.push(0);
+					self.new_line();
+				}
+				// Note: The `>` of the `->` of function pointers closes nothing.
+				'>' if self.last != '-' => {
+					if self.brackets.len() > 1 {
+						self.brackets.pop();
+					}
+					self.new_line();
+					self.buffer.push('>');
+				}
+				'(' | '[' => {
+					*self.open_brackets() += 1;
+					self.buffer.push(c);
+				}
+				')' | ']' => {
+					let open = self.open_brackets();
+					*open = open.saturating_sub(1);
+					self.buffer.push(c);
+				}
+				',' if self.brackets.len() > 1 && *self.open_brackets() == 0 => {
+					self.buffer.push(',');
+					self.new_line();
+					self.skip_space = true;
+				}
+				' ' if skip_space => (),
+				c => self.buffer.push(c),
+			}
+			self.last = c;
+		}
+		Ok(())
+	}
+}
+
+/// Returns the name of the given type escaped for embedding into HTML.
+///
+/// Escapes `&` as `&amp;`, `<` as `&lt;` and `>` as `&gt;`,
+/// e.g. `&Vec<u32>` is returned as `&amp;Vec&lt;u32&gt;`.
+#[cfg(feature = "alloc")]
+pub fn html_escaped_type_name<T>() -> String
+where
+	T: TypeName + ?Sized
+{
+	let mut writer = HtmlEscapeWriter(String::with_capacity(type_name_len::<T>()));
+	T::write_type_name(&mut writer)
+		.expect("[tyname::html_escaped_type_name] Encountered error while writing type name");
+	writer.0
+}
+
+/// A writer that escapes the HTML special characters of type names.
+#[cfg(feature = "alloc")]
+struct HtmlEscapeWriter(String);
+
+#[cfg(feature = "alloc")]
+impl Write for HtmlEscapeWriter {
+	fn write_str(&mut self, s: &str) -> FmtResult {
+		for c in s.chars() {
+			match c {
+				'&' => self.0.push_str("&amp;"),
+				'<' => self.0.push_str("&lt;"),
+				'>' => self.0.push_str("&gt;"),
+				c => self.0.push(c),
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Writes the name of the given type into the I/O writer `w`.
+///
+/// The name is streamed into `w` without building an intermediate
+/// `String` first. Consider wrapping `w` into a `BufWriter` since
+/// the name is written in many small pieces.
+///
+/// # Errors
+///
+/// If writing into `w` fails.
+///
+/// # Example
+///
+/// ```
+/// # use tyname::write_type_name_io;
+/// let mut bytes = Vec::new();
+/// write_type_name_io::<Option<u32>, _>(&mut bytes).unwrap();
+/// assert_eq!(bytes, b"Option<u32>");
+/// ```
+#[cfg(feature = "std")]
+pub fn write_type_name_io<T, W>(w: &mut W) -> std::io::Result<()>
+where
+	T: TypeName + ?Sized,
+	W: std::io::Write,
+{
+	let mut writer = IoWriter { inner: w, error: None };
+	match T::write_type_name(&mut writer) {
+		Ok(()) => Ok(()),
+		Err(_) => Err(writer.error.unwrap_or_else(|| {
+			std::io::Error::other("[tyname::write_type_name_io] Encountered error while writing type name")
+		})),
+	}
+}
+
+/// Adapts an I/O writer to `fmt::Write`, keeping the underlying I/O error.
+#[cfg(feature = "std")]
+struct IoWriter<'a, W> {
+	inner: &'a mut W,
+	error: Option<std::io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W> Write for IoWriter<'_, W>
+where
+	W: std::io::Write,
+{
+	fn write_str(&mut self, s: &str) -> FmtResult {
+		self.inner.write_all(s.as_bytes()).map_err(|error| {
+			self.error = Some(error);
+			core::fmt::Error
+		})
+	}
+}
+
+/// Returns the path of the module that defines the given type.
+///
+/// Standard library types report the path under which they are
+/// reachable from `std`, e.g. `std::boxed` for `Box<T>`.
+/// Returns `None` for primitives, tuples and other built-in types.
+///
+/// The paths are returned as `&'static str` instead of an owned `String`
+/// since they are known at compile time, so this neither allocates nor
+/// requires the `alloc` feature.
+pub fn module_path_of<T>() -> Option<&'static str>
+where
+	T: TypeName + ?Sized
+{
+	T::MODULE_PATH
+}
+
+/// Returns the statically known name of the given type, if any.
+///
+/// This allows to name types without generic arguments without writing
+/// their name, see [`TypeName::NAME`].
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// # use tyname::static_type_name;
+/// assert_eq!(static_type_name::<u32>(), Some("u32"));
+/// assert_eq!(static_type_name::<Vec<u32>>(), None);
+/// # }
+/// ```
+pub fn static_type_name<T>() -> Option<&'static str>
+where
+	T: TypeName + ?Sized
+{
+	T::NAME
+}
+
+/// Returns the length in bytes of the name of the given type.
+///
+/// This is equal to `type_name::<T>().len()` but does not allocate.
+pub fn type_name_len<T>() -> usize
+where
+	T: TypeName + ?Sized
+{
+	let mut counter = ByteCountWriter(0);
+	T::write_type_name(&mut counter)
+		.expect("[tyname::type_name_len] Encountered error while counting type name");
+	counter.0
+}
+
+/// Writes the name of the given type into the formatter `f`.
+///
+/// This is a shorthand for [`TypeName::write_type_name`] for use
+/// within manual `Debug` or `Display` impls.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use core::{fmt, marker::PhantomData};
+///
+/// struct Id<T>(u32, PhantomData<T>);
+///
+/// impl<T> fmt::Debug for Id<T>
+/// where
+///     T: tyname::TypeName
+/// {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         f.write_str("Id<")?;
+///         tyname::fmt_type_name::<T>(f)?;
+///         write!(f, ">({})", self.0)
+///     }
+/// }
+///
+/// assert_eq!(format!("{:?}", Id::<String>(7, PhantomData)), "Id<String>(7)");
+/// # }
+/// ```
+pub fn fmt_type_name<T>(f: &mut core::fmt::Formatter) -> FmtResult
+where
+	T: TypeName + ?Sized
+{
+	T::write_type_name(f)
+}
+
+/// Writes the name of the type `T` as `Debug` output into the formatter `f`.
+///
+/// Useful for opaque wrappers whose `Debug` output should read as the
+/// name of the wrapped type. Followed by `f.debug_struct("")` the fields
+/// of a value can be formatted after the name without allocating it as
+/// done by the `DebugTypeName` derive.
+///
+/// # Example
+///
+/// ```
+/// use core::fmt;
+///
+/// struct Opaque<T>(T);
+///
+/// impl<T> fmt::Debug for Opaque<T>
+/// where
+///     T: tyname::TypeName
+/// {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         tyname::debug_with_type_name::<T>(f)
+///     }
+/// }
+///
+/// assert_eq!(format!("{:?}", Opaque(42_i32)), "i32");
+/// assert_eq!(format!("{:?}", Opaque([Some('a')])), "[Option<char>; 1]");
+/// ```
+pub fn debug_with_type_name<T>(f: &mut core::fmt::Formatter) -> FmtResult
+where
+	T: TypeName + ?Sized
+{
+	core::fmt::Debug::fmt(&Named::<T>::new(), f)
+}
+
+/// Formats the single field of a tuple struct or variant after its name.
+///
+/// Unlike `f.debug_tuple("")` this writes no trailing comma, e.g. `(42)`
+/// instead of `(42,)`. Used by the `DebugTypeName` derive.
+#[doc(hidden)]
+pub fn __debug_single_field(f: &mut core::fmt::Formatter, field: &dyn core::fmt::Debug) -> FmtResult {
+	if f.alternate() {
+		return f.debug_tuple("").field(field).finish()
+	}
+	f.write_str("(")?;
+	field.fmt(f)?;
+	f.write_str(")")
+}
+
+/// Returns the length in bytes of the `Debug` output of the given value.
+///
+/// Used by the `TypeName` derive to hint the length of const generic arguments.
+#[doc(hidden)]
+pub fn __debug_len(value: &dyn core::fmt::Debug) -> usize {
+	let mut counter = ByteCountWriter(0);
+	match write!(counter, "{:?}", value) {
+		Ok(()) => counter.0,
+		Err(_) => 0,
+	}
+}
+
+/// Displays the name of the type `T` without allocating.
+///
+/// Useful to print type names within manual `Debug` or `Display` impls.
+///
+/// # Example
+///
+/// ```
+/// # use tyname::Named;
+/// struct Opaque<T>(T);
+///
+/// impl<T> core::fmt::Debug for Opaque<T>
+/// where
+///     T: tyname::TypeName
+/// {
+///     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+///         write!(f, "Opaque({})", Named::<T>::new())
+///     }
+/// }
+///
+/// assert_eq!(format!("{:?}", Opaque(42_i32)), "Opaque(i32)");
+/// ```
+pub struct Named<T>(PhantomData<fn() -> *const T>)
+where
+	T: ?Sized;
+
+impl<T> Named<T>
+where
+	T: TypeName + ?Sized
+{
+	/// Creates a new displayable type name for the type `T`.
+	pub fn new() -> Self {
+		Named(PhantomData)
+	}
+}
+
+/// Alias of [`Named`] under the name used by [`display_type_name`].
+pub type TypeNameDisplay<T> = Named<T>;
+
+/// Returns a displayable name of the type `T` that does not allocate.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// println!("{}", tyname::display_type_name::<Vec<i32>>());
+/// # }
+/// ```
+pub fn display_type_name<T>() -> TypeNameDisplay<T>
+where
+	T: TypeName + ?Sized
+{
+	Named::new()
+}
+
+impl<T> Default for Named<T>
+where
+	T: TypeName + ?Sized
+{
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> core::fmt::Display for Named<T>
+where
+	T: TypeName + ?Sized
+{
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> FmtResult {
+		T::write_type_name(f)
+	}
+}
+
+impl<T> core::fmt::Debug for Named<T>
+where
+	T: TypeName + ?Sized
+{
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> FmtResult {
+		T::write_type_name(f)
+	}
+}
+
+/// The type-erased name of some type.
+///
+/// Stores a function producing the name of the type so that the names
+/// of heterogeneous types can be stored together, e.g. in a `Vec`.
+///
+/// # Example
+///
+/// ```
+/// # use tyname::ErasedTypeName;
+/// let erased = vec![
+///     ErasedTypeName::of::<u32>(),
+///     ErasedTypeName::of::<Vec<u8>>(),
+/// ];
+/// let names = erased.iter().map(ErasedTypeName::name).collect::<Vec<_>>();
+/// assert_eq!(names, ["u32", "Vec<u8>"]);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Copy, Clone)]
+pub struct ErasedTypeName {
+	name: fn() -> String,
+}
+
+#[cfg(feature = "alloc")]
+impl ErasedTypeName {
+	/// Creates the type-erased name of the type `T`.
+	pub fn of<T>() -> Self
+	where
+		T: TypeName + ?Sized
+	{
+		ErasedTypeName { name: type_name::<T> }
+	}
+
+	/// Returns the name of the erased type.
+	pub fn name(&self) -> String {
+		(self.name)()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for ErasedTypeName {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> FmtResult {
+		f.write_str(&self.name())
+	}
+}
+
+/// A stand-in for types without a [`TypeName`] impl.
+///
+/// Always written as `<unknown>` regardless of `T`, so generic
+/// tooling can degrade gracefully instead of failing to compile.
+/// With [`FormatConfig::tokenizable`] it is written as the
+/// inferred type `_` instead.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// # use tyname::{type_name, Unknown};
+/// struct Opaque;
+///
+/// assert_eq!(type_name::<Vec<Unknown<Opaque>>>(), "Vec<<unknown>>");
+/// # }
+/// ```
+pub struct Unknown<T>(PhantomData<fn() -> *const T>)
+where
+	T: ?Sized;
+
+impl<T> TypeName for Unknown<T>
+where
+	T: ?Sized
+{
+	fn write_type_name<W>(w: &mut W) -> FmtResult where W: Write {
+		Self::write_type_name_with(w, &FormatConfig::default())
+	}
+
+	fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+		if config.tokenizable {
+			return w.write_str("_")
+		}
+		w.write_str("<unknown>")
+	}
+
+	fn type_name_len_hint() -> usize {
+		"<unknown>".len()
+	}
+}
+
+/// A writer that only counts the bytes written to it.
+///
+/// Useful to compute the size of some output up front without
+/// allocating, e.g. in order to pre-allocate a properly sized buffer.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// # use tyname::{ByteCountWriter, TypeName};
+/// let mut counter = ByteCountWriter(0);
+/// <Vec<i32> as TypeName>::write_type_name(&mut counter).unwrap();
+/// assert_eq!(counter.0, "Vec<i32>".len());
+/// # }
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ByteCountWriter(pub usize);
+
+impl Write for ByteCountWriter {
+	fn write_str(&mut self, s: &str) -> FmtResult {
+		self.0 += s.len();
+		Ok(())
+	}
+}
+
+/// Returns `true` if the registered trait object name has additional
+/// bounds, i.e. a `+` outside of its generic arguments.
+///
+/// Used by [`impl_type_name_dyn!`](crate::impl_type_name_dyn).
+#[doc(hidden)]
+pub const fn __has_additional_bounds(name: &str) -> bool {
+	let bytes = name.as_bytes();
+	let mut depth = 0;
+	let mut n = 0;
+	while n < bytes.len() {
+		match bytes[n] {
+			// Note: The `>` of the `->` of `Fn` traits closes nothing.
+			b'>' if n > 0 && bytes[n - 1] == b'-' => (),
+			b'<' | b'(' | b'[' => depth += 1,
+			b'>' | b')' | b']' => depth -= 1,
+			b'+' if depth == 0 => return true,
+			_ => (),
+		}
+		n += 1;
+	}
+	false
+}
+
+/// Implements [`TypeName`] for a trait object type.
+///
+/// Trait objects cannot implement [`TypeName`] automatically since the
+/// name of the trait is unknown. This registers the given name for the
+/// trait object so that it composes with all other impls, e.g. with
+/// `Box<T>`.
+///
+/// The given name should include the `dyn` keyword which is omitted
+/// when [`FormatConfig::legacy_dyn`] is set.
+///
+/// # Note
+///
+/// The impl is for the `'static` trait object, e.g. `dyn Trait + 'static`
+/// as found in `Box<dyn Trait>`. Due to the orphan rule only trait objects
+/// of traits defined in the invoking crate can be registered.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// # use tyname::{impl_type_name_dyn, type_name};
+/// trait Shape {}
+///
+/// impl_type_name_dyn!(dyn Shape, "dyn Shape");
+///
+/// assert_eq!(type_name::<Box<dyn Shape>>(), "Box<dyn Shape>");
+/// # }
+/// ```
+///
+/// The name can also be given with the `=>` syntax of
+/// [`impl_type_name!`](crate::impl_type_name).
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// # use tyname::{impl_type_name_dyn, type_name};
+/// trait Shape {}
+///
+/// impl_type_name_dyn!(dyn Shape + Send => "dyn Shape + Send");
+///
+/// assert_eq!(type_name::<Box<dyn Shape + Send>>(), "Box<dyn Shape + Send>");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! impl_type_name_dyn {
+	( $ty:ty => $repr:expr ) => {
+		$crate::impl_type_name_dyn!($ty, $repr);
+	};
+	( $ty:ty, $repr:expr ) => {
+		impl $crate::TypeName for $ty {
+			const IS_DYN_SUM: bool = $crate::__has_additional_bounds($repr);
+			const NAME: ::core::option::Option<&'static str> = ::core::option::Option::Some($repr);
+
+			fn write_type_name<W>(w: &mut W) -> $crate::FmtResult
+			where
+				W: ::core::fmt::Write,
+			{
+				<Self as $crate::TypeName>::write_type_name_with(w, &$crate::FormatConfig::default())
+			}
+
+			fn write_type_name_with<W>(w: &mut W, config: &$crate::FormatConfig) -> $crate::FmtResult
+			where
+				W: ::core::fmt::Write,
+			{
+				config.write_dyn_name(w, $repr)
+			}
+		}
+	};
+}
+
+/// Implements [`TypeName`] for a type without generic arguments.
+///
+/// The type is written as the given name. This is a lightweight
+/// alternative to `#[derive(TypeName)]` without the proc-macro
+/// dependency. The module path is the one of the module invoking
+/// the macro.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// # use tyname::{impl_type_name, type_name};
+/// struct Meters(i64);
+///
+/// impl_type_name!(Meters => "Meters");
+///
+/// assert_eq!(type_name::<Vec<Meters>>(), "Vec<Meters>");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! impl_type_name {
+	( $ty:ty => $repr:expr ) => {
+		impl $crate::TypeName for $ty {
+			const MODULE_PATH: ::core::option::Option<&'static str> =
+				::core::option::Option::Some(::core::module_path!());
+			const NAME: ::core::option::Option<&'static str> = ::core::option::Option::Some($repr);
+
+			fn write_type_name<W>(w: &mut W) -> $crate::FmtResult
+			where
+				W: ::core::fmt::Write,
+			{
+				<Self as $crate::TypeName>::write_type_name_with(w, &$crate::FormatConfig::default())
+			}
+
+			fn write_type_name_with<W>(w: &mut W, config: &$crate::FormatConfig) -> $crate::FmtResult
+			where
+				W: ::core::fmt::Write,
+			{
+				config.write_name(w, <Self as $crate::TypeName>::MODULE_PATH, $repr)
+			}
+		}
+	};
+}
+
+/// Implements [`TypeName`] for a type with two generic type parameters.
+///
+/// The type is written as the given name followed by its two generic
+/// arguments, e.g. `MyMap<u32, String>`. The module path is the one of
+/// the module invoking the macro.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// # use tyname::{impl_type_name_generic2, type_name};
+/// struct MyMap<K, V>(Vec<(K, V)>);
+///
+/// impl_type_name_generic2!(MyMap, "MyMap");
+///
+/// assert_eq!(type_name::<MyMap<u32, String>>(), "MyMap<u32, String>");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! impl_type_name_generic2 {
+	( $head:ident $(:: $seg:ident)* , $repr:expr ) => {
+		impl<K, V> $crate::TypeName for $head $(:: $seg)* <K, V>
+		where
+			K: $crate::TypeName,
+			V: $crate::TypeName,
+		{
+			const MODULE_PATH: ::core::option::Option<&'static str> =
+				::core::option::Option::Some(::core::module_path!());
+			const GENERIC_ARITY: usize = 2;
+
+			fn write_type_name<W>(w: &mut W) -> $crate::FmtResult
+			where
+				W: ::core::fmt::Write,
+			{
+				<Self as $crate::TypeName>::write_type_name_with(w, &$crate::FormatConfig::default())
+			}
+
+			fn write_type_name_with<W>(w: &mut W, config: &$crate::FormatConfig) -> $crate::FmtResult
+			where
+				W: ::core::fmt::Write,
+			{
+				config.write_name(w, <Self as $crate::TypeName>::MODULE_PATH, $repr)?;
+				w.write_str("<")?;
+				config.write_generic_arg::<K, W>(w)?;
+				config.write_comma(w)?;
+				config.write_generic_arg::<V, W>(w)?;
+				w.write_str(">")
+			}
+
+			fn type_name_len_hint() -> usize {
+				$repr.len()
+					+ <K as $crate::TypeName>::type_name_len_hint()
+					+ <V as $crate::TypeName>::type_name_len_hint()
+					+ "<, >".len()
+			}
+		}
+	};
+}
+
+/// Names a foreign type via a local newtype wrapper.
+///
+/// Due to the orphan rule [`TypeName`] cannot be implemented for a type
+/// of another crate unless either is defined in the implementing crate.
+/// This defines the newtype `$name` wrapping the foreign type and
+/// implements [`TypeName`] for the newtype, written as the given name.
+/// The module path is the one of the module invoking the macro.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// # use tyname::{name_foreign_type, type_name};
+/// # mod foreign { pub struct Handle(pub u32); }
+/// name_foreign_type!(pub Handle, foreign::Handle, "Handle");
+///
+/// let handle = Handle(foreign::Handle(42));
+/// assert_eq!(type_name::<Vec<Handle>>(), "Vec<Handle>");
+/// assert_eq!((handle.0).0, 42);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! name_foreign_type {
+	( $vis:vis $name:ident, $ty:ty, $repr:expr ) => {
+		// Note: The orphan rule forbids implementing the foreign trait
+		//       `TypeName` for the foreign type `$ty`, so the impl is for
+		//       this local newtype instead.
+		#[doc = ::core::concat!("Names `", ::core::stringify!($ty), "` as `", $repr, "`.")]
+		#[repr(transparent)]
+		$vis struct $name(pub $ty);
+
+		impl ::core::convert::From<$ty> for $name {
+			fn from(value: $ty) -> Self {
+				$name(value)
+			}
+		}
+
+		impl $crate::TypeName for $name {
+			const MODULE_PATH: ::core::option::Option<&'static str> =
+				::core::option::Option::Some(::core::module_path!());
+			const NAME: ::core::option::Option<&'static str> = ::core::option::Option::Some($repr);
+
+			fn write_type_name<W>(w: &mut W) -> $crate::FmtResult
+			where
+				W: ::core::fmt::Write,
+			{
+				<Self as $crate::TypeName>::write_type_name_with(w, &$crate::FormatConfig::default())
+			}
+
+			fn write_type_name_with<W>(w: &mut W, config: &$crate::FormatConfig) -> $crate::FmtResult
+			where
+				W: ::core::fmt::Write,
+			{
+				config.write_name(w, <Self as $crate::TypeName>::MODULE_PATH, $repr)
+			}
+		}
+	};
+}
+
+/// Implements `write_type_name` by forwarding to `write_type_name_with`
+/// using the default configuration.
+macro_rules! forward_write_type_name {
+	() => {
+		fn write_type_name<W>(w: &mut W) -> FmtResult where W: Write {
+			Self::write_type_name_with(w, &FormatConfig::default())
+		}
+	}
+}
+
 macro_rules! impl_tuple_signature_hash {
 	// Specialization for the unit type (void)
 	( ) => {
 		impl TypeName for () {
-			fn write_type_name<W>(w: &mut W) -> Result where W: Write {
+			const IS_UNIT: bool = true;
+			const NAME: Option<&'static str> = Some("()");
+
+			forward_write_type_name!();
+
+			fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+				if config.unit_as_word {
+					return w.write_str("unit")
+				}
 				w.write_str("()")
 			}
 		}
@@ -44,13 +1544,21 @@ macro_rules! impl_tuple_signature_hash {
 		where
 			$head: TypeName,
 		{
-			fn write_type_name<W>(w: &mut W) -> Result where W: Write {
+			const GENERIC_ARITY: usize = 1;
+
+			forward_write_type_name!();
+
+			fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
 				w.write_str("(")?;
-				$head::write_type_name(w)?;
+				$head::write_type_name_with(w, config)?;
 				// Comma needed here to differentiate between
 				// parenthesized expressions and unary-tuples
 				w.write_str(",)")
 			}
+
+			fn type_name_len_hint() -> usize {
+				$head::type_name_len_hint() + "(,)".len()
+			}
 		}
 
 		impl_tuple_signature_hash!();
@@ -62,15 +1570,23 @@ macro_rules! impl_tuple_signature_hash {
 			$head: TypeName,
 			$( $tail: TypeName, )*
 		{
-			fn write_type_name<W>(w: &mut W) -> Result where W: Write {
+			const GENERIC_ARITY: usize = 1 + [$(stringify!($tail)),*].len();
+
+			forward_write_type_name!();
+
+			fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
 				w.write_str("(")?;
-				$head::write_type_name(w)?;
+				$head::write_type_name_with(w, config)?;
 				$(
-					w.write_str(", ")?;
-					$tail::write_type_name(w)?;
+					config.write_comma(w)?;
+					$tail::write_type_name_with(w, config)?;
 				)*
 				w.write_str(")")
 			}
+
+			fn type_name_len_hint() -> usize {
+				"()".len() + $head::type_name_len_hint() $( + ", ".len() + $tail::type_name_len_hint() )*
+			}
 		}
 
 		// Strip head and recurse the implementation.
@@ -79,99 +1595,159 @@ macro_rules! impl_tuple_signature_hash {
 }
 
 impl_tuple_signature_hash!(
-	T0 T1 T2 T3 T4 T5 T6 T7 T8 T9
+	T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11
 );
 
 /// Implementation for raw function-pointer types.
 ///
 /// # Note
 ///
-/// The current implementation outputs the return type even for
-/// functions that have a unit (`()`) return type and thus should
-/// not display it - at least that's the behaviour of the intrinsic.
-/// E.g. this currently writes `fn() -> ()` instead of just `fn()`.
+/// Just like the intrinsic the unit (`()`) return type is not written,
+/// e.g. this writes `fn(i32)` instead of `fn(i32) -> ()`.
+///
+/// The qualifiers in brackets, e.g. `[unsafe extern "C"]`, precede `fn` in the
+/// implemented type and are written as the given prefix.
+///
+/// The first identifier names the return type and the others the
+/// parameter types, so the ladders below support up to 12 parameters.
+///
+/// Higher-ranked function pointers such as `fn(&u8)`, i.e. `for<'a> fn(&'a u8)`,
+/// are distinct types which are not implemented since impls for them
+/// overlap with the impls below according to the future-incompatible
+/// `coherence_leak_check` lint. Function pointers with explicit lifetimes,
+/// e.g. `fn(&'static u8)`, are supported.
 macro_rules! impl_fn_signature_hash {
 	// Base case for no parameter types.
-	( $ret:ident ) => {
-		impl<$ret> TypeName for fn() -> $ret
+	( [$($qual:tt)*] $prefix:expr; $ret:ident ) => {
+		impl<$ret> TypeName for $($qual)* fn() -> $ret
 		where
 			$ret: TypeName
 		{
-			fn write_type_name<W>(w: &mut W) -> Result where W: Write {
-				w.write_str("fn() -> ")?;
-				$ret::write_type_name(w)
+			forward_write_type_name!();
+
+			fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+				w.write_str($prefix)?;
+				w.write_str("fn()")?;
+				write_fn_return::<$ret, W>(w, config)
+			}
+
+			fn type_name_len_hint() -> usize {
+				$prefix.len() + "fn()".len() + fn_return_len_hint::<$ret>()
 			}
 		}
 	};
 	// Impl for generic parameters and return type.
-	( $ret:ident $head:ident $($tail:ident)* ) => {
-		impl<$ret, $head, $($tail),*> TypeName for fn($head, $($tail),*) -> $ret
+	( [$($qual:tt)*] $prefix:expr; $ret:ident $head:ident $($tail:ident)* ) => {
+		impl<$ret, $head, $($tail),*> TypeName for $($qual)* fn($head, $($tail),*) -> $ret
 		where
 			$ret: TypeName,
 			$head: TypeName,
 			$( $tail: TypeName, )*
 		{
-			fn write_type_name<W>(w: &mut W) -> Result where W: Write {
+			forward_write_type_name!();
+
+			fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+				w.write_str($prefix)?;
 				w.write_str("fn(")?;
-				$head::write_type_name(w)?;
+				$head::write_type_name_with(w, config)?;
 				$(
-					w.write_str(",")?;
-					$tail::write_type_name(w)?;
+					config.write_comma(w)?;
+					$tail::write_type_name_with(w, config)?;
 				)*
-				w.write_str(") -> ")?;
-				$ret::write_type_name(w)
+				w.write_str(")")?;
+				write_fn_return::<$ret, W>(w, config)
+			}
+
+			fn type_name_len_hint() -> usize {
+				$prefix.len()
+					+ "fn()".len()
+					+ $head::type_name_len_hint()
+					$( + ", ".len() + $tail::type_name_len_hint() )*
+					+ fn_return_len_hint::<$ret>()
 			}
 		}
 
 		// Strip head type and recurse to simplify caller.
-		impl_fn_signature_hash!( $ret $($tail)* );
+		impl_fn_signature_hash!( [$($qual)*] $prefix; $ret $($tail)* );
 	}
 }
 
-impl_fn_signature_hash!(
-	T0 T1 T2 T3 T4 T5 T6 T7 T8 T9
-);
+/// Writes the return type `R` of a function unless it is the unit type.
+fn write_fn_return<R, W>(w: &mut W, config: &FormatConfig) -> FmtResult
+where
+	R: TypeName,
+	W: Write,
+{
+	if R::IS_UNIT {
+		return Ok(())
+	}
+	w.write_str(" -> ")?;
+	R::write_type_name_with(w, config)
+}
 
-macro_rules! impl_array_signature_hash {
-	( $($n:expr)* ) => {
-		$(
-			impl<T> TypeName for [T; $n]
-			where
-				T: TypeName
-			{
-				fn write_type_name<W>(w: &mut W) -> Result where W: Write {
-					w.write_str("[")?;
-					T::write_type_name(w)?;
-					w.write_str("; ")?;
-					write!(w, "{}", $n)?;
-					w.write_str("]")
-				}
-			}
-		)*
-	};
+/// Returns the length hint of the return type `R` written by [`write_fn_return`].
+fn fn_return_len_hint<R>() -> usize
+where
+	R: TypeName,
+{
+	if R::IS_UNIT {
+		return 0
+	}
+	" -> ".len() + R::type_name_len_hint()
 }
 
-impl_array_signature_hash!(
-	// All from 1 to 32
-	 1  2  3  4  5  6  7  8  9 10
-	11 12 13 14 15 16 17 18 19 20
-	21 22 23 24 25 26 27 28 29 30
-	31 32
-	// Powers of two
-	64 128 256 512 1024 2048 4096
-	// Some specialized array lengths
-	160 192
+impl_fn_signature_hash!(
+	[] "";
+	T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12
+);
+impl_fn_signature_hash!(
+	[extern "C"] "extern \"C\" ";
+	T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12
+);
+impl_fn_signature_hash!(
+	[unsafe] "unsafe ";
+	T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12
+);
+impl_fn_signature_hash!(
+	[unsafe extern "C"] "unsafe extern \"C\" ";
+	T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12
 );
 
+impl<T, const N: usize> TypeName for [T; N]
+where
+	T: TypeName
+{
+	forward_write_type_name!();
+
+	fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+		w.write_str("[")?;
+		T::write_type_name_with(w, config)?;
+		w.write_str("; ")?;
+		config.write_array_len(w, N)?;
+		w.write_str("]")
+	}
+
+	fn type_name_len_hint() -> usize {
+		let digits = N.checked_ilog10().map_or(1, |log| log as usize + 1);
+		T::type_name_len_hint() + digits + "[; ]".len()
+	}
+}
+
 impl<T> TypeName for [T]
 where
 	T: TypeName
 {
-	fn write_type_name<W>(w: &mut W) -> Result where W: Write {
+	forward_write_type_name!();
+
+	fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
 		w.write_str("[")?;
-		T::write_type_name(w)?;
+		T::write_type_name_with(w, config)?;
 		w.write_str("]")
 	}
+
+	fn type_name_len_hint() -> usize {
+		T::type_name_len_hint() + "[]".len()
+	}
 }
 
 /// Implementation macro for raw-pointers and references.
@@ -181,9 +1757,20 @@ macro_rules! impl_ptrref_signature_hash {
 		where
 			T: TypeName + ?Sized
 		{
-			fn write_type_name<W>(w: &mut W) -> Result where W: Write {
+			forward_write_type_name!();
+
+			fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
 				w.write_str($prefix)?;
-				T::write_type_name(w)
+				if config.tokenizable && T::IS_DYN_SUM {
+					w.write_str("(")?;
+					T::write_type_name_with(w, config)?;
+					return w.write_str(")")
+				}
+				T::write_type_name_with(w, config)
+			}
+
+			fn type_name_len_hint() -> usize {
+				$prefix.len() + T::type_name_len_hint()
 			}
 		}
 	}
@@ -194,97 +1781,720 @@ impl_ptrref_signature_hash!("&mut ", &mut);
 impl_ptrref_signature_hash!("*const ", *const);
 impl_ptrref_signature_hash!("*mut ", *mut);
 
+/// Implementation macro for the smart pointers.
+///
+/// Pass `@alloc` first if the smart pointer is generic over an allocator
+/// with the `allocator_api` feature.
+#[cfg(feature = "alloc")]
 macro_rules! impl_smartptr_signature_hash {
-	( $head:ident $(:: $seg:ident)* , $repr:expr ) => {
+	( @alloc $head:ident $(:: $seg:ident)* , $repr:expr, $path:expr ) => {
+		#[cfg(not(feature = "allocator_api"))]
+		impl_smartptr_signature_hash!( $head $(:: $seg)*, $repr, $path );
+		#[cfg(feature = "allocator_api")]
+		impl<T, A> TypeName for $head $(:: $seg)* <T, A>
+		where
+			T: TypeName + ?Sized,
+			A: core::alloc::Allocator,
+		{
+			impl_smartptr_signature_hash!( @body $repr, $path );
+		}
+	};
+	( $head:ident $(:: $seg:ident)* , $repr:expr, $path:expr ) => {
 		impl<T> TypeName for $head $(:: $seg)* <T>
 		where
 			T: TypeName + ?Sized
 		{
-			fn write_type_name<W>(w: &mut W) -> Result where W: Write {
-				w.write_str($repr)?;
-				w.write_str("<")?;
-				T::write_type_name(w)?;
-				w.write_str(">")
+			impl_smartptr_signature_hash!( @body $repr, $path );
+		}
+	};
+	( @body $repr:expr, $path:expr ) => {
+		const MODULE_PATH: Option<&'static str> = Some($path);
+		const GENERIC_ARITY: usize = 1;
+
+		forward_write_type_name!();
+
+		fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+			if config.c_pointer_smartptrs {
+				T::write_type_name_with(w, config)?;
+				return w.write_str("*")
 			}
+			config.write_name(w, Self::MODULE_PATH, $repr)?;
+			w.write_str("<")?;
+			config.write_generic_arg::<T, W>(w)?;
+			w.write_str(">")
 		}
-	}
+
+		fn type_name_len_hint() -> usize {
+			$repr.len() + T::type_name_len_hint() + "<>".len()
+		}
+	};
 }
 
-impl_smartptr_signature_hash!(Box, "Box");
-impl_smartptr_signature_hash!(std::rc::Rc, "Rc");
-impl_smartptr_signature_hash!(std::sync::Arc, "Arc");
+// Note: `Box` is generic over an allocator `A` defaulting to `Global`.
+//       The `Allocator` trait is unstable so on stable Rust only `Box<T>`
+//       with the default allocator can be named. With the `allocator_api`
+//       feature any allocator is accepted. Either way the allocator is
+//       never written, just like `std::any::type_name` does not write it.
+#[cfg(feature = "alloc")]
+impl_smartptr_signature_hash!(@alloc alloc::boxed::Box, "Box", "std::boxed");
+#[cfg(feature = "alloc")]
+impl_smartptr_signature_hash!(@alloc alloc::rc::Rc, "Rc", "std::rc");
+#[cfg(feature = "alloc")]
+impl_smartptr_signature_hash!(@alloc alloc::sync::Arc, "Arc", "std::sync");
 
+/// Implementation macro for types with exactly one generic type parameter.
+///
+/// Pass `?Sized` as last argument if the type parameter may be unsized.
+/// Pass `@alloc` first if the type is generic over an allocator with the
+/// `allocator_api` feature.
 macro_rules! impl_collections_signature_hash {
-	( $head:ident $(:: $seg:ident)* , $repr:expr ) => {
+	( @alloc $head:ident $(:: $seg:ident)* , $repr:expr, $path:expr ) => {
+		#[cfg(not(feature = "allocator_api"))]
+		impl_collections_signature_hash!( $head $(:: $seg)*, $repr, $path );
+		#[cfg(feature = "allocator_api")]
+		impl<T, A> TypeName for $head $(:: $seg)* <T, A>
+		where
+			T: TypeName,
+			A: core::alloc::Allocator,
+		{
+			impl_collections_signature_hash!( @body $repr, $path );
+		}
+	};
+	( $head:ident $(:: $seg:ident)* , $repr:expr, $path:expr $(, ?$sized:ident)? ) => {
 		impl<T> TypeName for $head $(:: $seg)* <T>
 		where
-			T: TypeName
+			T: TypeName $(+ ?$sized)?
 		{
-			fn write_type_name<W>(w: &mut W) -> Result where W: Write {
-				w.write_str($repr)?;
-				w.write_str("<")?;
-				T::write_type_name(w)?;
-				w.write_str(">")
-			}
+			impl_collections_signature_hash!( @body $repr, $path );
+		}
+	};
+	( @body $repr:expr, $path:expr ) => {
+		const MODULE_PATH: Option<&'static str> = Some($path);
+		const GENERIC_ARITY: usize = 1;
+
+		forward_write_type_name!();
+
+		fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+			config.write_name(w, Self::MODULE_PATH, $repr)?;
+			w.write_str("<")?;
+			config.write_generic_arg::<T, W>(w)?;
+			w.write_str(">")
+		}
+
+		fn type_name_len_hint() -> usize {
+			$repr.len() + T::type_name_len_hint() + "<>".len()
 		}
+	};
+}
+
+impl_collections_signature_hash!( Option, "Option", "std::option" );
+#[cfg(feature = "alloc")]
+impl_collections_signature_hash!( @alloc alloc::vec::Vec, "Vec", "std::vec" );
+#[cfg(feature = "alloc")]
+impl_collections_signature_hash!( @alloc alloc::collections::VecDeque, "VecDeque", "std::collections" );
+#[cfg(feature = "alloc")]
+impl_collections_signature_hash!( @alloc alloc::collections::LinkedList, "LinkedList", "std::collections" );
+// Note: `BTreeSet` requires its allocator to be `Clone` and is thus not
+//       generic over the allocator with the `allocator_api` feature.
+#[cfg(feature = "alloc")]
+impl_collections_signature_hash!( alloc::collections::BTreeSet, "BTreeSet", "std::collections" );
+#[cfg(feature = "alloc")]
+impl_collections_signature_hash!( @alloc alloc::collections::BinaryHeap, "BinaryHeap", "std::collections" );
+#[cfg(feature = "std")]
+impl_collections_signature_hash!( std::sync::mpsc::SendError, "SendError", "std::sync::mpsc" );
+#[cfg(feature = "std")]
+impl_collections_signature_hash!( std::sync::Mutex, "Mutex", "std::sync", ?Sized );
+#[cfg(feature = "std")]
+impl_collections_signature_hash!( std::sync::RwLock, "RwLock", "std::sync", ?Sized );
+#[cfg(feature = "std")]
+impl_collections_signature_hash!( std::sync::OnceLock, "OnceLock", "std::sync" );
+impl_collections_signature_hash!( core::marker::PhantomData, "PhantomData", "std::marker", ?Sized );
+impl_collections_signature_hash!( core::mem::ManuallyDrop, "ManuallyDrop", "std::mem", ?Sized );
+impl_collections_signature_hash!( core::mem::MaybeUninit, "MaybeUninit", "std::mem" );
+// Note: `NonNull<T>` is written in its nominal form as it is spelled in
+//       source code rather than as the `*mut T` it wraps.
+impl_collections_signature_hash!( core::ptr::NonNull, "NonNull", "std::ptr", ?Sized );
+// Note: The cells are no pointers and thus are not written as such
+//       with `FormatConfig::c_pointer_smartptrs`.
+impl_collections_signature_hash!( core::cell::Cell, "Cell", "std::cell", ?Sized );
+impl_collections_signature_hash!( core::cell::RefCell, "RefCell", "std::cell", ?Sized );
+impl_collections_signature_hash!( core::cell::UnsafeCell, "UnsafeCell", "std::cell", ?Sized );
+impl_collections_signature_hash!( core::cell::OnceCell, "OnceCell", "std::cell" );
+impl_collections_signature_hash!( core::num::Wrapping, "Wrapping", "std::num" );
+impl_collections_signature_hash!( core::num::Saturating, "Saturating", "std::num" );
+impl_collections_signature_hash!( core::cmp::Reverse, "Reverse", "std::cmp" );
+// Note: Both `rc::Weak` and `sync::Weak` are written as `Weak`.
+//       Use `FormatConfig::qualified` to distinguish them.
+#[cfg(feature = "alloc")]
+impl_collections_signature_hash!( alloc::rc::Weak, "Weak", "std::rc", ?Sized );
+#[cfg(feature = "alloc")]
+impl_collections_signature_hash!( alloc::sync::Weak, "Weak", "std::sync", ?Sized );
+// Note: The generic argument of `Pin` is the pointer type, not the pointee.
+impl_collections_signature_hash!( core::pin::Pin, "Pin", "std::pin" );
+impl_collections_signature_hash!( core::ops::Range, "Range", "std::ops" );
+impl_collections_signature_hash!( core::ops::RangeInclusive, "RangeInclusive", "std::ops" );
+impl_collections_signature_hash!( core::ops::RangeFrom, "RangeFrom", "std::ops" );
+impl_collections_signature_hash!( core::ops::RangeTo, "RangeTo", "std::ops" );
+impl_collections_signature_hash!( core::ops::RangeToInclusive, "RangeToInclusive", "std::ops" );
+#[cfg(feature = "alloc")]
+impl_collections_signature_hash!( alloc::vec::IntoIter, "IntoIter", "std::vec" );
+impl_collections_signature_hash!( core::iter::StepBy, "StepBy", "std::iter" );
+impl_collections_signature_hash!( core::iter::Take, "Take", "std::iter" );
+impl_collections_signature_hash!( core::iter::Skip, "Skip", "std::iter" );
+
+/// Implementation for `HashMap`.
+///
+/// The hasher `S` is not written, so for any hasher the output is `HashMap<K, V>`.
+#[cfg(feature = "std")]
+impl<K, V, S> TypeName for std::collections::HashMap<K, V, S>
+where
+	K: TypeName,
+	V: TypeName,
+	S: std::hash::BuildHasher,
+{
+	const MODULE_PATH: Option<&'static str> = Some("std::collections");
+	const GENERIC_ARITY: usize = 2;
+
+	forward_write_type_name!();
+
+	fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+		config.write_name(w, Self::MODULE_PATH, "HashMap")?;
+		w.write_str("<")?;
+		config.write_generic_arg::<K, W>(w)?;
+		config.write_comma(w)?;
+		config.write_generic_arg::<V, W>(w)?;
+		w.write_str(">")
+	}
+
+	fn type_name_len_hint() -> usize {
+		"HashMap".len() + K::type_name_len_hint() + V::type_name_len_hint() + "<, >".len()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> TypeName for alloc::collections::BTreeMap<K, V>
+where
+	K: TypeName,
+	V: TypeName,
+{
+	const MODULE_PATH: Option<&'static str> = Some("std::collections");
+	const GENERIC_ARITY: usize = 2;
+
+	forward_write_type_name!();
+
+	fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+		config.write_name(w, Self::MODULE_PATH, "BTreeMap")?;
+		w.write_str("<")?;
+		config.write_generic_arg::<K, W>(w)?;
+		config.write_comma(w)?;
+		config.write_generic_arg::<V, W>(w)?;
+		w.write_str(">")
+	}
+
+	fn type_name_len_hint() -> usize {
+		"BTreeMap".len() + K::type_name_len_hint() + V::type_name_len_hint() + "<, >".len()
+	}
+}
+
+/// Implementation for `HashSet`.
+///
+/// The hasher `S` is not written, so for any hasher the output is `HashSet<T>`.
+/// The set is unordered, which is not reflected by its name.
+#[cfg(feature = "std")]
+impl<T, S> TypeName for std::collections::HashSet<T, S>
+where
+	T: TypeName,
+	S: std::hash::BuildHasher,
+{
+	const MODULE_PATH: Option<&'static str> = Some("std::collections");
+	const GENERIC_ARITY: usize = 1;
+
+	forward_write_type_name!();
+
+	fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+		config.write_name(w, Self::MODULE_PATH, "HashSet")?;
+		w.write_str("<")?;
+		config.write_generic_arg::<T, W>(w)?;
+		w.write_str(">")
+	}
+
+	fn type_name_len_hint() -> usize {
+		"HashSet".len() + T::type_name_len_hint() + "<>".len()
+	}
+}
+
+/// Implementation macro for types with exactly two generic type parameters.
+macro_rules! impl_pair_signature_hash {
+	( $( $head:ident $(:: $seg:ident)* , $repr:expr , $path:expr );* $(;)? ) => {
+		$(
+			impl<A, B> TypeName for $head $(:: $seg)* <A, B>
+			where
+				A: TypeName,
+				B: TypeName,
+			{
+				const MODULE_PATH: Option<&'static str> = Some($path);
+				const GENERIC_ARITY: usize = 2;
+
+				forward_write_type_name!();
+
+				fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+					config.write_name(w, Self::MODULE_PATH, $repr)?;
+					w.write_str("<")?;
+					config.write_generic_arg::<A, W>(w)?;
+					config.write_comma(w)?;
+					config.write_generic_arg::<B, W>(w)?;
+					w.write_str(">")
+				}
+
+				fn type_name_len_hint() -> usize {
+					$repr.len() + A::type_name_len_hint() + B::type_name_len_hint() + "<, >".len()
+				}
+			}
+		)*
 	}
 }
 
-impl_collections_signature_hash!( Option, "Option" );
-impl_collections_signature_hash!( Vec, "Vec" );
-impl_collections_signature_hash!( std::collections::VecDeque, "VecDeque" );
-impl_collections_signature_hash!( std::collections::LinkedList, "LinkedList" );
+impl_pair_signature_hash!(
+	core::iter::Chain, "Chain", "std::iter";
+	core::iter::Zip, "Zip", "std::iter";
+);
 
-impl<T, E> TypeName for std::result::Result<T, E>
+impl<T, E> TypeName for core::result::Result<T, E>
 where
 	T: TypeName,
 	E: TypeName,
 {
-	fn write_type_name<W>(w: &mut W) -> Result where W: Write {
-		w.write_str("Result<")?;
-		T::write_type_name(w)?;
-		w.write_str(", ")?;
-		E::write_type_name(w)?;
+	const MODULE_PATH: Option<&'static str> = Some("std::result");
+	const GENERIC_ARITY: usize = 2;
+
+	forward_write_type_name!();
+
+	fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+		config.write_name(w, Self::MODULE_PATH, "Result")?;
+		w.write_str("<")?;
+		config.write_generic_arg::<T, W>(w)?;
+		config.write_comma(w)?;
+		config.write_generic_arg::<E, W>(w)?;
+		w.write_str(">")
+	}
+
+	fn type_name_len_hint() -> usize {
+		"Result".len() + T::type_name_len_hint() + E::type_name_len_hint() + "<, >".len()
+	}
+}
+
+/// Implementation for `ControlFlow<B, C = ()>`.
+///
+/// # Note
+///
+/// The defaulted continue type `C` is elided if it is `()` so that
+/// `ControlFlow<B>` and `ControlFlow<B, ()>` are both written as
+/// `ControlFlow<B>`, i.e. the way users usually write the type.
+impl<B, C> TypeName for core::ops::ControlFlow<B, C>
+where
+	B: TypeName,
+	C: TypeName,
+{
+	const MODULE_PATH: Option<&'static str> = Some("std::ops");
+	const GENERIC_ARITY: usize = if C::IS_UNIT { 1 } else { 2 };
+
+	forward_write_type_name!();
+
+	fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+		config.write_name(w, Self::MODULE_PATH, "ControlFlow")?;
+		w.write_str("<")?;
+		config.write_generic_arg::<B, W>(w)?;
+		if !C::IS_UNIT {
+			config.write_comma(w)?;
+			config.write_generic_arg::<C, W>(w)?;
+		}
 		w.write_str(">")
 	}
+
+	fn type_name_len_hint() -> usize {
+		let continue_len = if C::IS_UNIT { 0 } else { ", ".len() + C::type_name_len_hint() };
+		"ControlFlow".len() + B::type_name_len_hint() + continue_len + "<>".len()
+	}
 }
 
-impl<'a, B> TypeName for std::borrow::Cow<'a, B>
+/// Implementation for `Cow<'a, B>`.
+///
+/// Writes `Cow<B>`, e.g. `Cow<str>` for `Cow<'a, str>`.
+/// By design neither the lifetime nor the owned type (`String` for `str`)
+/// are written, matching how the type is spelled in source code.
+///
+/// The owned type can be named through its `ToOwned` projection instead:
+///
+/// ```
+/// # use tyname::type_name;
+/// assert_eq!(type_name::<<str as ToOwned>::Owned>(), "String");
+/// assert_eq!(type_name::<<[u8] as ToOwned>::Owned>(), "Vec<u8>");
+/// ```
+#[cfg(feature = "alloc")]
+impl<'a, B> TypeName for alloc::borrow::Cow<'a, B>
 where
-	B: 'a + ToOwned + ?Sized + TypeName
+	B: 'a + alloc::borrow::ToOwned + ?Sized + TypeName
 {
-	fn write_type_name<W>(w: &mut W) -> Result where W: Write {
-		w.write_str("Cow<")?;
-		B::write_type_name(w)?;
+	const MODULE_PATH: Option<&'static str> = Some("std::borrow");
+	const GENERIC_ARITY: usize = 1;
+
+	forward_write_type_name!();
+
+	fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+		config.write_name(w, Self::MODULE_PATH, "Cow")?;
+		w.write_str("<")?;
+		config.write_generic_arg::<B, W>(w)?;
 		w.write_str(">")
 	}
+
+	fn type_name_len_hint() -> usize {
+		"Cow".len() + B::type_name_len_hint() + "<>".len()
+	}
+}
+
+/// Implementation for the borrowing iterators of the collections.
+///
+/// The lifetime of the borrowed collection is not part of the name.
+#[cfg(feature = "alloc")]
+macro_rules! impl_borrowing_iter_signature_hash {
+	( $( $head:ident $(:: $seg:ident)* , $repr:expr , $module:expr );* $(;)? ) => {
+		$(
+			impl<'a, T> TypeName for $head $(:: $seg)* <'a, T>
+			where
+				T: TypeName,
+			{
+				const MODULE_PATH: Option<&'static str> = Some($module);
+				const GENERIC_ARITY: usize = 1;
+
+				forward_write_type_name!();
+
+				fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+					config.write_name(w, Self::MODULE_PATH, $repr)?;
+					w.write_str("<")?;
+					config.write_generic_arg::<T, W>(w)?;
+					w.write_str(">")
+				}
+
+				fn type_name_len_hint() -> usize {
+					$repr.len() + T::type_name_len_hint() + "<>".len()
+				}
+			}
+		)*
+	}
+}
+
+// Note: The iterators keep their module prefix in order to not
+//       be confused with the iterators of other collections.
+#[cfg(feature = "alloc")]
+impl_borrowing_iter_signature_hash!(
+	alloc::collections::linked_list::Iter, "linked_list::Iter", "std::collections::linked_list";
+	alloc::collections::linked_list::IterMut, "linked_list::IterMut", "std::collections::linked_list";
+);
+
+/// Implementation for the borrowing key-value types of the map APIs,
+/// e.g. the entry types of the `HashMap` entry API.
+///
+/// The lifetime of the borrowed map is not part of the name.
+#[cfg(feature = "alloc")]
+macro_rules! impl_entry_signature_hash {
+	( $( $head:ident $(:: $seg:ident)* , $repr:expr , $module:expr );* $(;)? ) => {
+		$(
+			impl<'a, K, V> TypeName for $head $(:: $seg)* <'a, K, V>
+			where
+				K: TypeName,
+				V: TypeName,
+			{
+				const MODULE_PATH: Option<&'static str> = Some($module);
+				const GENERIC_ARITY: usize = 2;
+
+				forward_write_type_name!();
+
+				fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+					config.write_name(w, Self::MODULE_PATH, $repr)?;
+					w.write_str("<")?;
+					config.write_generic_arg::<K, W>(w)?;
+					config.write_comma(w)?;
+					config.write_generic_arg::<V, W>(w)?;
+					w.write_str(">")
+				}
+
+				fn type_name_len_hint() -> usize {
+					$repr.len() + K::type_name_len_hint() + V::type_name_len_hint() + "<, >".len()
+				}
+			}
+		)*
+	}
+}
+
+// Note: `btree_map::Range` keeps its module prefix in order to not be
+//       confused with `std::ops::Range`.
+#[cfg(feature = "std")]
+impl_entry_signature_hash!(
+	std::collections::hash_map::Entry, "Entry", "std::collections::hash_map";
+	std::collections::hash_map::OccupiedEntry, "OccupiedEntry", "std::collections::hash_map";
+	std::collections::hash_map::VacantEntry, "VacantEntry", "std::collections::hash_map";
+);
+#[cfg(feature = "alloc")]
+impl_entry_signature_hash!(
+	alloc::collections::btree_map::Range, "btree_map::Range", "std::collections::btree_map";
+);
+
+/// Implementation for `std::num::NonZero<T>`.
+///
+/// `NonZero<T>` can only be instantiated with the primitive integer
+/// types so we implement the trait for every one of them.
+///
+/// # Note
+///
+/// The old `NonZeroU32`, `NonZeroI8`, etc. are type aliases for
+/// `NonZero<u32>`, `NonZero<i8>`, etc. and thus are written as
+/// `NonZero<u32>`, `NonZero<i8>`, etc. as well.
+macro_rules! impl_nonzero_signature_hash {
+	( $($ty:ty),* ) => {
+		$(
+			impl TypeName for core::num::NonZero<$ty> {
+				const MODULE_PATH: Option<&'static str> = Some("std::num");
+				const GENERIC_ARITY: usize = 1;
+
+				forward_write_type_name!();
+
+				fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+					config.write_name(w, Self::MODULE_PATH, "NonZero")?;
+					w.write_str("<")?;
+					config.write_generic_arg::<$ty, W>(w)?;
+					w.write_str(">")
+				}
+
+				fn type_name_len_hint() -> usize {
+					"NonZero".len() + <$ty>::type_name_len_hint() + "<>".len()
+				}
+			}
+		)*
+	}
+}
+
+impl_nonzero_signature_hash!(
+	u8, u16, u32, u64, u128, usize,
+	i8, i16, i32, i64, i128, isize
+);
+
+/// Implementation for the primitive integer and float types.
+///
+/// With [`FormatConfig::bitwidth_primitives`] these are written by their
+/// kind and bit-width, e.g. `uint<32>` for `u32`. The pointer-sized
+/// `usize` and `isize` are written with the bit-width of the target.
+macro_rules! impl_number_signature_hash {
+	( $( $ty:ty => $repr:expr, $kind:expr, $bits:expr );* $(;)? ) => {
+		$(
+			impl TypeName for $ty {
+				const NAME: Option<&'static str> = Some($repr);
+
+				forward_write_type_name!();
+
+				fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+					if config.bitwidth_primitives {
+						return write!(w, "{}<{}>", $kind, $bits)
+					}
+					w.write_str($repr)
+				}
+			}
+		)*
+	}
 }
 
+/// Implementation for types without generic arguments.
+///
+/// # Note
+///
+/// The re-exports of `std::primitive` and `core::primitive` are the very
+/// same types as the primitives and thus are guaranteed to be written with
+/// their bare names, e.g. `std::primitive::u32` is written as `u32`.
+///
+/// Many types of the same module can be implemented at once by listing
+/// them after the module path, each optionally preceded by attributes:
+///
+/// ```ignore
+/// impl_naive_signature_hash!(
+///     "std::time";
+///     core::time::Duration => "Duration",
+///     #[cfg(feature = "std")]
+///     std::time::Instant => "Instant",
+/// );
+/// ```
 macro_rules! impl_naive_signature_hash {
-	( $ty:ident, $repr:expr ) => {
+	( $path:literal; $( $(#[$attr:meta])* $ty:ty => $repr:expr ),+ $(,)? ) => {
+		$(
+			$(#[$attr])*
+			impl_naive_signature_hash!($ty, $repr, $path);
+		)+
+	};
+	( $ty:ty, $repr:expr $(, $path:expr)? ) => {
 		impl TypeName for $ty {
-			fn write_type_name<W>(w: &mut W) -> Result where W: Write {
-				w.write_str($repr)
+			$( const MODULE_PATH: Option<&'static str> = Some($path); )?
+			const NAME: Option<&'static str> = Some($repr);
+
+			forward_write_type_name!();
+
+			fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+				config.write_name(w, Self::MODULE_PATH, $repr)
 			}
 		}
 	}
 }
 
-impl_naive_signature_hash!(String, "String");
+#[cfg(feature = "alloc")]
+impl_naive_signature_hash!(alloc::string::String, "String", "std::string");
 impl_naive_signature_hash!(str, "str");
 impl_naive_signature_hash!(bool, "bool");
 impl_naive_signature_hash!(char, "char");
-impl_naive_signature_hash!(u8, "u8");
-impl_naive_signature_hash!(u16, "u16");
-impl_naive_signature_hash!(u32, "u32");
-impl_naive_signature_hash!(u64, "u64");
-impl_naive_signature_hash!(u128, "u128");
-impl_naive_signature_hash!(usize, "usize");
-impl_naive_signature_hash!(i8, "i8");
-impl_naive_signature_hash!(i16, "i16");
-impl_naive_signature_hash!(i32, "i32");
-impl_naive_signature_hash!(i64, "i64");
-impl_naive_signature_hash!(i128, "i128");
-impl_naive_signature_hash!(isize, "isize");
-impl_naive_signature_hash!(f32, "f32");
-impl_naive_signature_hash!(f64, "f64");
+impl_number_signature_hash!(
+	u8 => "u8", "uint", 8;
+	u16 => "u16", "uint", 16;
+	u32 => "u32", "uint", 32;
+	u64 => "u64", "uint", 64;
+	u128 => "u128", "uint", 128;
+	usize => "usize", "uint", usize::BITS;
+	i8 => "i8", "int", 8;
+	i16 => "i16", "int", 16;
+	i32 => "i32", "int", 32;
+	i64 => "i64", "int", 64;
+	i128 => "i128", "int", 128;
+	isize => "isize", "int", isize::BITS;
+	f32 => "f32", "float", 32;
+	f64 => "f64", "float", 64;
+);
+impl_naive_signature_hash!(core::fmt::Formatter<'_>, "Formatter", "std::fmt");
+impl_naive_signature_hash!(
+	"std::net";
+	core::net::IpAddr => "IpAddr",
+	core::net::Ipv4Addr => "Ipv4Addr",
+	core::net::Ipv6Addr => "Ipv6Addr",
+	core::net::SocketAddr => "SocketAddr",
+	core::net::SocketAddrV4 => "SocketAddrV4",
+	core::net::SocketAddrV6 => "SocketAddrV6",
+);
+// Note: The other C types of `core::ffi` such as `c_int` are type aliases
+//       for primitives and are therefore written as those, e.g. as `i32`.
+impl_naive_signature_hash!(core::ffi::c_void, "c_void", "std::ffi");
+impl_naive_signature_hash!(core::ffi::CStr, "CStr", "std::ffi");
+#[cfg(feature = "alloc")]
+impl_naive_signature_hash!(alloc::ffi::CString, "CString", "std::ffi");
+#[cfg(feature = "std")]
+impl_naive_signature_hash!(std::path::Path, "Path", "std::path");
+#[cfg(feature = "std")]
+impl_naive_signature_hash!(std::path::PathBuf, "PathBuf", "std::path");
+#[cfg(feature = "std")]
+impl_naive_signature_hash!(std::ffi::OsStr, "OsStr", "std::ffi");
+#[cfg(feature = "std")]
+impl_naive_signature_hash!(std::ffi::OsString, "OsString", "std::ffi");
+impl_naive_signature_hash!(core::marker::PhantomPinned, "PhantomPinned", "std::marker");
+impl_naive_signature_hash!(core::convert::Infallible, "Infallible", "std::convert");
+impl_naive_signature_hash!(
+	"std::time";
+	core::time::Duration => "Duration",
+	#[cfg(feature = "std")]
+	std::time::Instant => "Instant",
+	#[cfg(feature = "std")]
+	std::time::SystemTime => "SystemTime",
+	#[cfg(feature = "std")]
+	std::time::SystemTimeError => "SystemTimeError",
+);
+// Note: The `fmt` and `io` errors keep their module prefix in order to
+//       distinguish them from each other and from user defined `Error`s.
+impl_naive_signature_hash!(core::fmt::Error, "fmt::Error", "std::fmt");
+#[cfg(feature = "std")]
+impl_naive_signature_hash!(std::io::Error, "io::Error", "std::io");
+impl_naive_signature_hash!(core::str::Utf8Error, "Utf8Error", "std::str");
+#[cfg(feature = "alloc")]
+impl_naive_signature_hash!(alloc::string::FromUtf8Error, "FromUtf8Error", "std::string");
+impl_naive_signature_hash!(
+	"std::num";
+	core::num::ParseIntError => "ParseIntError",
+	core::num::ParseFloatError => "ParseFloatError",
+);
+impl_naive_signature_hash!(core::cell::BorrowError, "BorrowError", "std::cell");
+impl_naive_signature_hash!(core::cell::BorrowMutError, "BorrowMutError", "std::cell");
+#[cfg(feature = "std")]
+impl_naive_signature_hash!(std::io::IoSlice<'_>, "IoSlice", "std::io");
+#[cfg(feature = "std")]
+impl_naive_signature_hash!(std::io::IoSliceMut<'_>, "IoSliceMut", "std::io");
+impl_naive_signature_hash!(core::ops::RangeFull, "RangeFull", "std::ops");
+// Note: The far more common `cmp::Ordering` is written as `Ordering` while
+//       the memory ordering keeps its module prefix to distinguish them.
+impl_naive_signature_hash!(core::cmp::Ordering, "Ordering", "std::cmp");
+impl_naive_signature_hash!(core::sync::atomic::Ordering, "atomic::Ordering", "std::sync::atomic");
+// Note: Not every target supports atomics of every width.
+impl_naive_signature_hash!(
+	"std::sync::atomic";
+	#[cfg(target_has_atomic = "8")]
+	core::sync::atomic::AtomicBool => "AtomicBool",
+	#[cfg(target_has_atomic = "8")]
+	core::sync::atomic::AtomicU8 => "AtomicU8",
+	#[cfg(target_has_atomic = "8")]
+	core::sync::atomic::AtomicI8 => "AtomicI8",
+	#[cfg(target_has_atomic = "16")]
+	core::sync::atomic::AtomicU16 => "AtomicU16",
+	#[cfg(target_has_atomic = "16")]
+	core::sync::atomic::AtomicI16 => "AtomicI16",
+	#[cfg(target_has_atomic = "32")]
+	core::sync::atomic::AtomicU32 => "AtomicU32",
+	#[cfg(target_has_atomic = "32")]
+	core::sync::atomic::AtomicI32 => "AtomicI32",
+	#[cfg(target_has_atomic = "64")]
+	core::sync::atomic::AtomicU64 => "AtomicU64",
+	#[cfg(target_has_atomic = "64")]
+	core::sync::atomic::AtomicI64 => "AtomicI64",
+	#[cfg(target_has_atomic = "ptr")]
+	core::sync::atomic::AtomicUsize => "AtomicUsize",
+	#[cfg(target_has_atomic = "ptr")]
+	core::sync::atomic::AtomicIsize => "AtomicIsize",
+);
+#[cfg(target_has_atomic = "ptr")]
+impl_collections_signature_hash!( core::sync::atomic::AtomicPtr, "AtomicPtr", "std::sync::atomic" );
+#[cfg(feature = "std")]
+impl_naive_signature_hash!(std::sync::mpsc::RecvError, "RecvError", "std::sync::mpsc");
+#[cfg(feature = "std")]
+impl_naive_signature_hash!(std::sync::mpsc::TryRecvError, "TryRecvError", "std::sync::mpsc");
+
+impl_type_name_dyn!(dyn core::any::Any, "dyn Any");
+impl_type_name_dyn!(dyn core::any::Any + Send, "dyn Any + Send");
+impl_type_name_dyn!(dyn core::any::Any + Send + Sync, "dyn Any + Send + Sync");
+impl_type_name_dyn!(dyn core::error::Error, "dyn Error");
+impl_type_name_dyn!(dyn core::error::Error + Send, "dyn Error + Send");
+impl_type_name_dyn!(dyn core::error::Error + Send + Sync, "dyn Error + Send + Sync");
+
+/// Implementation for the `dyn Future<Output = T>` trait objects.
+///
+/// The associated `Output` type is written like a generic argument,
+/// e.g. `Pin<Box<dyn Future<Output = ()>>>` as used by async executors.
+macro_rules! impl_future_dyn_signature_hash {
+	( $( ( $($bound:ident),* ) => $suffix:expr );* $(;)? ) => {
+		$(
+			impl<T> TypeName for dyn core::future::Future<Output = T> $(+ $bound)*
+			where
+				T: TypeName,
+			{
+				const IS_DYN_SUM: bool = !$suffix.is_empty();
+
+				forward_write_type_name!();
+
+				fn write_type_name_with<W>(w: &mut W, config: &FormatConfig) -> FmtResult where W: Write {
+					config.write_dyn_name(w, "dyn Future<Output = ")?;
+					config.write_generic_arg::<T, W>(w)?;
+					w.write_str(">")?;
+					w.write_str($suffix)
+				}
+
+				fn type_name_len_hint() -> usize {
+					"dyn Future<Output = >".len() + T::type_name_len_hint() + $suffix.len()
+				}
+			}
+		)*
+	}
+}
+
+impl_future_dyn_signature_hash!(
+	() => "";
+	(Send) => " + Send";
+	(Send, Sync) => " + Send + Sync";
+);
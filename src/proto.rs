@@ -0,0 +1,91 @@
+//! Protocol Buffers scalar type names.
+
+use crate::{render, FmtResult};
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+
+/// Types that can write their name as Protocol Buffers type.
+///
+/// Rust primitives map to the Protocol Buffers scalar types,
+/// e.g. `u32` to `uint32` and `f64` to `double`, while `Vec<T>`
+/// maps to `repeated T`.
+///
+/// # Note
+///
+/// Protocol Buffers do not support nested repeated fields,
+/// so the output for e.g. `Vec<Vec<u32>>` is not a valid field type.
+pub trait ProtoTypeName {
+	/// Writes the Protocol Buffers name of `Self`.
+	fn write_proto_type_name<W>(writer: &mut W) -> FmtResult
+	where
+		W: Write;
+
+	/// Writes the Protocol Buffers name of a `Vec<Self>`.
+	///
+	/// Defaults to `repeated` followed by the name of `Self`. This is a hook
+	/// for element types with a dedicated representation, e.g. `Vec<u8>` is
+	/// written as `bytes`.
+	fn write_proto_repeated<W>(writer: &mut W) -> FmtResult
+	where
+		W: Write,
+	{
+		writer.write_str("repeated ")?;
+		Self::write_proto_type_name(writer)
+	}
+}
+
+/// Returns the Protocol Buffers name of the given type.
+pub fn proto_type_name<T>() -> String
+where
+	T: ProtoTypeName + ?Sized
+{
+	render("proto_type_name", |mut w| T::write_proto_type_name(&mut w))
+}
+
+macro_rules! impl_proto_scalar {
+	( $( $ty:ty => $repr:expr ),* $(,)? ) => {
+		$(
+			impl ProtoTypeName for $ty {
+				fn write_proto_type_name<W>(w: &mut W) -> FmtResult where W: Write {
+					w.write_str($repr)
+				}
+			}
+		)*
+	}
+}
+
+impl_proto_scalar!(
+	bool => "bool",
+	u16 => "uint32",
+	u32 => "uint32",
+	u64 => "uint64",
+	usize => "uint64",
+	i8 => "int32",
+	i16 => "int32",
+	i32 => "int32",
+	i64 => "int64",
+	isize => "int64",
+	f32 => "float",
+	f64 => "double",
+	str => "string",
+	String => "string",
+);
+
+impl ProtoTypeName for u8 {
+	fn write_proto_type_name<W>(w: &mut W) -> FmtResult where W: Write {
+		w.write_str("uint32")
+	}
+
+	fn write_proto_repeated<W>(w: &mut W) -> FmtResult where W: Write {
+		w.write_str("bytes")
+	}
+}
+
+impl<T> ProtoTypeName for Vec<T>
+where
+	T: ProtoTypeName
+{
+	fn write_proto_type_name<W>(w: &mut W) -> FmtResult where W: Write {
+		T::write_proto_repeated(w)
+	}
+}
@@ -0,0 +1,53 @@
+//! Structural signatures of types.
+
+use crate::{hash::Fnv1aWriter, FmtResult, TypeName};
+#[cfg(feature = "alloc")]
+use crate::render;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::fmt::Write;
+
+/// Types that can write a structural signature of themselves.
+///
+/// The structural signature consists of the name of the type followed by
+/// the names and types of its fields, e.g. `Point{x:f32,y:f32}`.
+/// Unlike the plain type name it changes whenever the layout of the
+/// type changes which makes it suitable for schema hashing.
+///
+/// Usually derived via `#[derive(TypeName)]` and `#[tyname(structural)]`
+/// which use the following formats:
+///
+/// - structs with named fields: `Point{x:f32,y:f32}`
+/// - tuple structs: `Meters(i64)`
+/// - unit structs: `Marker`
+/// - enums: `Shape{Empty,Circle(f32),Rect{w:f32,h:f32}}`
+pub trait StructuralTypeName: TypeName {
+	/// Writes the structural signature of `Self`.
+	fn write_structural_type_name<W>(writer: &mut W) -> FmtResult
+	where
+		W: Write;
+}
+
+/// Returns the structural signature of the given type.
+#[cfg(feature = "alloc")]
+pub fn structural_type_name<T>() -> String
+where
+	T: StructuralTypeName + ?Sized
+{
+	render("structural_type_name", |mut w| T::write_structural_type_name(&mut w))
+}
+
+/// Returns a deterministic hash of the structural signature of the given type.
+///
+/// The hash changes whenever the name or the fields of the type change
+/// which allows to detect schema changes of serialized data. This uses
+/// FNV-1a so the hash is stable across runs and platforms.
+pub fn structural_signature_hash<T>() -> u64
+where
+	T: StructuralTypeName + ?Sized
+{
+	let mut hasher = Fnv1aWriter::new();
+	T::write_structural_type_name(&mut hasher)
+		.expect("[tyname::structural_signature_hash] Encountered error while hashing structural type name");
+	hasher.finish()
+}
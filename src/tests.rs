@@ -1,4 +1,7 @@
-use crate::{TypeName, type_name};
+use crate::{
+	TypeName, ByteCountWriter, ContainerTypeName, FormatConfig, Named, TypeNameTree,
+	module_path_of, type_name, type_name_len, type_name_with,
+};
 
 /// Asserts that the type name of the given generic
 /// type parameter equals the given expected string.
@@ -7,6 +10,7 @@ where
 	T: TypeName + ?Sized
 {
 	assert_eq!(type_name::<T>(), String::from(expected));
+	assert_eq!(T::type_name_len_hint(), expected.len(), "length hint of {}", expected);
 }
 
 #[test]
@@ -31,6 +35,64 @@ fn simple() {
 	assert_type_name::<f64>("f64");
 }
 
+#[test]
+fn bitwidth() {
+	use crate::bitwidth_type_name;
+
+	assert_eq!(bitwidth_type_name::<u32>(), "uint<32>");
+	assert_eq!(bitwidth_type_name::<i8>(), "int<8>");
+	assert_eq!(bitwidth_type_name::<f64>(), "float<64>");
+	assert_eq!(bitwidth_type_name::<Vec<u32>>(), "Vec<uint<32>>");
+	assert_eq!(bitwidth_type_name::<(bool, [i128; 2])>(), "(bool, [int<128>; 2])");
+	assert_eq!(
+		bitwidth_type_name::<usize>(),
+		format!("uint<{}>", std::mem::size_of::<usize>() * 8)
+	);
+	assert_type_name::<u32>("u32");
+}
+
+#[test]
+fn generic_arity() {
+	use std::{collections::HashMap, ops::ControlFlow};
+
+	assert_eq!(u32::GENERIC_ARITY, 0);
+	assert_eq!(<()>::GENERIC_ARITY, 0);
+	assert_eq!(<Vec<u32>>::GENERIC_ARITY, 1);
+	assert_eq!(<Box<str>>::GENERIC_ARITY, 1);
+	assert_eq!(<Result<u32, String>>::GENERIC_ARITY, 2);
+	assert_eq!(<HashMap<u32, String>>::GENERIC_ARITY, 2);
+	assert_eq!(<ControlFlow<u32>>::GENERIC_ARITY, 1);
+	assert_eq!(<ControlFlow<u32, u8>>::GENERIC_ARITY, 2);
+	assert_eq!(<(u8,)>::GENERIC_ARITY, 1);
+	assert_eq!(<(u8, u8, u8)>::GENERIC_ARITY, 3);
+	assert_eq!(<&Vec<u32>>::GENERIC_ARITY, 0);
+}
+
+#[test]
+fn unit_as_word() {
+	let config = FormatConfig {
+		unit_as_word: true,
+		..FormatConfig::default()
+	};
+	assert_type_name::<()>("()");
+	assert_eq!(type_name_with::<()>(&config), "unit");
+	assert_eq!(type_name_with::<Result<(), String>>(&config), "Result<unit, String>");
+	assert_eq!(type_name_with::<((), ((),))>(&config), "(unit, (unit,))");
+	assert_eq!(type_name_with::<fn() -> ()>(&config), "fn()");
+}
+
+#[test]
+fn primitive_reexports() {
+	assert_type_name::<std::primitive::bool>("bool");
+	assert_type_name::<std::primitive::u32>("u32");
+	assert_type_name::<std::primitive::str>("str");
+	assert_type_name::<core::primitive::i32>("i32");
+	assert_type_name::<core::primitive::f64>("f64");
+	assert_type_name::<Vec<core::primitive::i32>>("Vec<i32>");
+	assert_type_name::<(std::primitive::char, &core::primitive::str)>("(char, &str)");
+	assert_eq!(module_path_of::<std::primitive::u32>(), None);
+}
+
 #[test]
 fn tuple() {
 	assert_type_name::<()>("()");
@@ -49,13 +111,54 @@ fn tuple() {
 
 #[test]
 fn raw_fn() {
-	// FIXME: remove the `-> ()` suffix for unit type
-	assert_type_name::<fn()>("fn() -> ()");
+	assert_type_name::<fn()>("fn()");
+	assert_type_name::<fn() -> ()>("fn()");
+	assert_type_name::<fn(i32)>("fn(i32)");
 	assert_type_name::<fn() -> bool>("fn() -> bool");
 	assert_type_name::<fn(i32) -> bool>("fn(i32) -> bool");
+	assert_type_name::<fn() -> ((),)>("fn() -> ((),)");
 	assert_type_name::<fn((i32,)) -> bool>("fn((i32,)) -> bool");
 }
 
+#[test]
+fn unit_param_and_return() {
+	assert_type_name::<fn(())>("fn(())");
+	assert_type_name::<fn(()) -> ()>("fn(())");
+	assert_type_name::<fn() -> ()>("fn()");
+	assert_type_name::<fn((), ()) -> ()>("fn((), ())");
+	assert_type_name::<fn(()) -> fn() -> ()>("fn(()) -> fn()");
+	assert_type_name::<unsafe extern "C" fn(()) -> ()>("unsafe extern \"C\" fn(())");
+}
+
+#[test]
+fn extern_c_fn() {
+	assert_type_name::<extern "C" fn()>("extern \"C\" fn()");
+	assert_type_name::<extern "C" fn(u8) -> u8>("extern \"C\" fn(u8) -> u8");
+	assert_type_name::<extern "C" fn(i32, *const u8) -> i32>("extern \"C\" fn(i32, *const u8) -> i32");
+	assert_type_name::<Option<extern "C" fn(usize)>>("Option<extern \"C\" fn(usize)>");
+	// The default ABI is not written.
+	assert_type_name::<fn(u8) -> u8>("fn(u8) -> u8");
+}
+
+#[test]
+fn unsafe_fn() {
+	assert_type_name::<unsafe fn()>("unsafe fn()");
+	assert_type_name::<unsafe fn(i32) -> bool>("unsafe fn(i32) -> bool");
+	assert_type_name::<unsafe fn(*const u8) -> usize>("unsafe fn(*const u8) -> usize");
+	assert_type_name::<unsafe extern "C" fn(*mut u8)>("unsafe extern \"C\" fn(*mut u8)");
+	assert_type_name::<Vec<unsafe fn() -> u8>>("Vec<unsafe fn() -> u8>");
+}
+
+#[test]
+fn fn_many_params() {
+	assert_type_name::<fn(i32, i32, i32, i32, i32, i32, i32, i32, i32, i32, i32)>(
+		"fn(i32, i32, i32, i32, i32, i32, i32, i32, i32, i32, i32)"
+	);
+	assert_type_name::<extern "C" fn(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, bool) -> i32>(
+		"extern \"C\" fn(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, bool) -> i32"
+	);
+}
+
 #[test]
 fn array() {
 	assert_type_name::<[u32; 1]>("[u32; 1]");
@@ -63,6 +166,57 @@ fn array() {
 	assert_type_name::<[u8; 2048]>("[u8; 2048]");
 	assert_type_name::<[(u8, i16); 10]>("[(u8, i16); 10]");
 	assert_type_name::<[[f32; 4]; 4]>("[[f32; 4]; 4]");
+	assert_type_name::<[u8; 0]>("[u8; 0]");
+	assert_type_name::<[i32; 7]>("[i32; 7]");
+	assert_type_name::<[u8; 48]>("[u8; 48]");
+	assert_type_name::<[bool; 1000]>("[bool; 1000]");
+}
+
+#[test]
+fn array_lengths() {
+	macro_rules! assert_array_lengths {
+		( $($n:literal)* ) => {
+			$( assert_type_name::<[u8; $n]>(&format!("[u8; {}]", $n)); )*
+		}
+	}
+	// All lengths that used to be listed explicitly before const generics.
+	assert_array_lengths!(
+		 1  2  3  4  5  6  7  8  9 10
+		11 12 13 14 15 16 17 18 19 20
+		21 22 23 24 25 26 27 28 29 30
+		31 32
+		64 128 256 512 1024 2048 4096
+		160 192
+	);
+}
+
+#[test]
+fn multi_dimensional_array() {
+	assert_type_name::<[[f32; 4]; 4]>("[[f32; 4]; 4]");
+	assert_type_name::<[[f32; 16]; 16]>("[[f32; 16]; 16]");
+	assert_type_name::<[[f32; 48]; 48]>("[[f32; 48]; 48]");
+	assert_type_name::<[[f64; 100]; 100]>("[[f64; 100]; 100]");
+	assert_type_name::<[[f32; 3]; 48]>("[[f32; 3]; 48]");
+	assert_type_name::<[[[f32; 4]; 4]; 4]>("[[[f32; 4]; 4]; 4]");
+	assert_type_name::<[[[u8; 16]; 16]; 16]>("[[[u8; 16]; 16]; 16]");
+	assert_type_name::<[[[f32; 48]; 100]; 48]>("[[[f32; 48]; 100]; 48]");
+	assert_type_name::<[[[f64; 100]; 100]; 100]>("[[[f64; 100]; 100]; 100]");
+}
+
+#[test]
+fn arrays_in_tuples() {
+	assert_type_name::<([u8; 32], [u8; 64])>("([u8; 32], [u8; 64])");
+	assert_type_name::<(String, [u8; 32])>("(String, [u8; 32])");
+	assert_type_name::<(u64, [u8; 32], [u8; 64])>("(u64, [u8; 32], [u8; 64])");
+	assert_type_name::<(u64, [u8; 48])>("(u64, [u8; 48])");
+	assert_type_name::<([u8; 48],)>("([u8; 48],)");
+}
+
+#[test]
+fn zero_length_array() {
+	assert_type_name::<[i32; 0]>("[i32; 0]");
+	assert_type_name::<[[u8; 0]; 4]>("[[u8; 0]; 4]");
+	assert_type_name::<Option<[(); 0]>>("Option<[(); 0]>");
 }
 
 #[test]
@@ -73,6 +227,16 @@ fn slice() {
 	assert_type_name::<[[i32; 4]]>("[[i32; 4]]");
 }
 
+#[test]
+fn mixed_slice_array_nesting() {
+	assert_type_name::<[&'static [i32]]>("[&[i32]]");
+	assert_type_name::<[*const u8]>("[*const u8]");
+	assert_type_name::<[Box<str>; 4]>("[Box<str>; 4]");
+	assert_type_name::<[&'static [u8; 4]; 2]>("[&[u8; 4]; 2]");
+	assert_type_name::<[Box<[u16]>]>("[Box<[u16]>]");
+	assert_type_name::<[*mut [u8]; 3]>("[*mut [u8]; 3]");
+}
+
 #[test]
 fn ptr_ref() {
 	assert_type_name::<&bool>("&bool");
@@ -91,6 +255,22 @@ fn ptr_ref() {
 	assert_type_name::<*mut [i32]>("*mut [i32]");
 }
 
+#[test]
+fn nested_ptr_ref() {
+	fn assert_lifetimes_elided<'a, 'b>(_: &'a &'b mut str) {
+		assert_type_name::<&'a &'b mut str>("&&mut str");
+		assert_type_name::<&'a mut &'b [u8]>("&mut &[u8]");
+	}
+
+	assert_type_name::<&&i32>("&&i32");
+	assert_type_name::<&&&bool>("&&&bool");
+	assert_type_name::<&mut &mut u8>("&mut &mut u8");
+	assert_type_name::<*const &i32>("*const &i32");
+	assert_type_name::<&*mut i32>("&*mut i32");
+	assert_type_name::<&'static &'static str>("&&str");
+	assert_lifetimes_elided(&&mut String::from("text")[..]);
+}
+
 #[test]
 fn smart_ptr() {
 	assert_type_name::<Box<i32>>("Box<i32>");
@@ -110,18 +290,24 @@ fn smart_ptr() {
 
 #[test]
 fn gen1_collections() {
-	use std::collections::{VecDeque, LinkedList};
+	use std::collections::{VecDeque, LinkedList, HashSet, BTreeSet, BinaryHeap};
 	use std::borrow::Cow;
 
 	assert_type_name::<Option<i32>>("Option<i32>");
 	assert_type_name::<Vec<i32>>("Vec<i32>");
 	assert_type_name::<VecDeque<i32>>("VecDeque<i32>");
 	assert_type_name::<LinkedList<i32>>("LinkedList<i32>");
+	assert_type_name::<HashSet<i32>>("HashSet<i32>");
+	assert_type_name::<BTreeSet<String>>("BTreeSet<String>");
+	assert_type_name::<BinaryHeap<u64>>("BinaryHeap<u64>");
 
 	assert_type_name::<Option<Box<str>>>("Option<Box<str>>");
 	assert_type_name::<Vec<Box<str>>>("Vec<Box<str>>");
 	assert_type_name::<VecDeque<Box<str>>>("VecDeque<Box<str>>");
 	assert_type_name::<LinkedList<Box<str>>>("LinkedList<Box<str>>");
+	assert_type_name::<HashSet<Box<str>>>("HashSet<Box<str>>");
+	assert_type_name::<BTreeSet<Vec<u8>>>("BTreeSet<Vec<u8>>");
+	assert_type_name::<BinaryHeap<(u32, Option<i8>)>>("BinaryHeap<(u32, Option<i8>)>");
 	assert_type_name::<Cow<String>>("Cow<String>");
 }
 
@@ -134,3 +320,2281 @@ fn gen2_collections() {
 	assert_type_name::<Result<i32, String>>("Result<i32, String>");
 	assert_type_name::<Result<(), String>>("Result<(), String>");
 }
+
+#[test]
+fn maps() {
+	use std::collections::{BTreeMap, HashMap};
+
+	assert_type_name::<HashMap<String, i32>>("HashMap<String, i32>");
+	assert_type_name::<BTreeMap<String, i32>>("BTreeMap<String, i32>");
+	assert_type_name::<BTreeMap<String, Vec<i32>>>("BTreeMap<String, Vec<i32>>");
+	assert_type_name::<HashMap<u8, BTreeMap<(u8, u8), Option<bool>>>>(
+		"HashMap<u8, BTreeMap<(u8, u8), Option<bool>>>"
+	);
+	assert_eq!(module_path_of::<BTreeMap<u8, u8>>(), Some("std::collections"));
+}
+
+#[test]
+fn btree_map_key_first() {
+	use std::collections::BTreeMap;
+
+	assert_type_name::<BTreeMap<u8, String>>("BTreeMap<u8, String>");
+	assert_type_name::<BTreeMap<String, u8>>("BTreeMap<String, u8>");
+	assert_type_name::<BTreeMap<(u32, u32), Vec<String>>>("BTreeMap<(u32, u32), Vec<String>>");
+}
+
+#[test]
+fn len() {
+	use std::borrow::Cow;
+
+	fn assert_len<T>()
+	where
+		T: TypeName + ?Sized
+	{
+		assert_eq!(type_name_len::<T>(), type_name::<T>().len());
+	}
+
+	assert_len::<()>();
+	assert_len::<u8>();
+	assert_len::<[u8; 32]>();
+	assert_len::<Result<i32, String>>();
+	assert_len::<Cow<str>>();
+	assert_len::<Cow<[u8]>>();
+	assert_len::<Cow<Box<str>>>();
+	assert_eq!(type_name_len::<Cow<str>>(), type_name::<Cow<str>>().len());
+}
+
+#[test]
+fn unknown() {
+	use crate::Unknown;
+
+	/// Does not implement `TypeName`.
+	struct WithoutImpl;
+
+	assert_type_name::<Unknown<WithoutImpl>>("<unknown>");
+	assert_type_name::<Unknown<str>>("<unknown>");
+	assert_type_name::<Option<Unknown<WithoutImpl>>>("Option<<unknown>>");
+}
+
+#[test]
+fn erased() {
+	use crate::ErasedTypeName;
+
+	let erased: Vec<ErasedTypeName> = vec![
+		ErasedTypeName::of::<u32>(),
+		ErasedTypeName::of::<Vec<u8>>(),
+		ErasedTypeName::of::<String>(),
+	];
+	let names = erased.into_iter().map(|erased| erased.name()).collect::<Vec<_>>();
+	assert_eq!(names, ["u32", "Vec<u8>", "String"]);
+	assert_eq!(ErasedTypeName::of::<str>().to_string(), "str");
+}
+
+#[test]
+fn byte_count_writer() {
+	use std::fmt::Write;
+
+	let mut counter = ByteCountWriter(0);
+	counter.write_str("").unwrap();
+	assert_eq!(counter.0, 0);
+	counter.write_str("Hello").unwrap();
+	counter.write_str(", ").unwrap();
+	counter.write_str("World!").unwrap();
+	assert_eq!(counter.0, 13);
+	counter.write_char('ä').unwrap();
+	assert_eq!(counter.0, 15);
+
+	let mut counter = ByteCountWriter::default();
+	<Result<(i32, u8), String>>::write_type_name(&mut counter).unwrap();
+	assert_eq!(counter.0, "Result<(i32, u8), String>".len());
+}
+
+#[test]
+fn nonzero() {
+	use std::num::{NonZero, NonZeroU32, NonZeroI8, NonZeroUsize};
+
+	assert_type_name::<NonZero<u8>>("NonZero<u8>");
+	assert_type_name::<NonZero<u32>>("NonZero<u32>");
+	assert_type_name::<NonZero<i128>>("NonZero<i128>");
+	assert_type_name::<NonZero<isize>>("NonZero<isize>");
+
+	// The old spellings are type aliases for the generic form.
+	assert_type_name::<NonZeroU32>("NonZero<u32>");
+	assert_type_name::<NonZeroI8>("NonZero<i8>");
+	assert_type_name::<NonZeroUsize>("NonZero<usize>");
+
+	assert_type_name::<Option<NonZero<u32>>>("Option<NonZero<u32>>");
+}
+
+#[test]
+fn nonzero_aliases() {
+	use std::num::*;
+
+	assert_type_name::<NonZeroU8>("NonZero<u8>");
+	assert_type_name::<NonZeroU16>("NonZero<u16>");
+	assert_type_name::<NonZeroU32>("NonZero<u32>");
+	assert_type_name::<NonZeroU64>("NonZero<u64>");
+	assert_type_name::<NonZeroU128>("NonZero<u128>");
+	assert_type_name::<NonZeroUsize>("NonZero<usize>");
+	assert_type_name::<NonZeroI8>("NonZero<i8>");
+	assert_type_name::<NonZeroI16>("NonZero<i16>");
+	assert_type_name::<NonZeroI32>("NonZero<i32>");
+	assert_type_name::<NonZeroI64>("NonZero<i64>");
+	assert_type_name::<NonZeroI128>("NonZero<i128>");
+	assert_type_name::<NonZeroIsize>("NonZero<isize>");
+	// The niche-optimized `Option` composes like any other.
+	assert_type_name::<Option<NonZeroU32>>("Option<NonZero<u32>>");
+}
+
+#[test]
+fn named() {
+	assert_eq!(format!("{}", Named::<u8>::new()), "u8");
+	assert_eq!(format!("{}", Named::<[Box<str>]>::new()), "[Box<str>]");
+	assert_eq!(format!("{:?}", Named::<Option<bool>>::default()), "Option<bool>");
+}
+
+#[test]
+fn channel_errors() {
+	use std::sync::mpsc::{SendError, RecvError, TryRecvError};
+
+	assert_type_name::<SendError<u32>>("SendError<u32>");
+	assert_type_name::<SendError<Vec<u8>>>("SendError<Vec<u8>>");
+	assert_type_name::<RecvError>("RecvError");
+	assert_type_name::<TryRecvError>("TryRecvError");
+	assert_type_name::<Result<u8, RecvError>>("Result<u8, RecvError>");
+}
+
+#[test]
+fn paren_all_args() {
+	use std::borrow::Cow;
+
+	let config = FormatConfig {
+		paren_all_args: true,
+		..FormatConfig::default()
+	};
+	assert_eq!(
+		type_name::<Vec<Result<u32, String>>>(),
+		"Vec<Result<u32, String>>"
+	);
+	assert_eq!(
+		type_name_with::<Vec<Result<u32, String>>>(&config),
+		"Vec<(Result<(u32), (String)>)>"
+	);
+	assert_eq!(
+		type_name_with::<Vec<Result<u32, String>>>(&FormatConfig::default()),
+		"Vec<Result<u32, String>>"
+	);
+	// Only generic arguments are parenthesized.
+	assert_eq!(
+		type_name_with::<(u8, [Box<str>; 2])>(&config),
+		"(u8, [Box<(str)>; 2])"
+	);
+	assert_eq!(
+		type_name_with::<&Cow<[Option<i8>]>>(&config),
+		"&Cow<([Option<(i8)>])>"
+	);
+}
+
+#[test]
+fn module_path() {
+	use std::{borrow::Cow, rc::Rc, sync::Arc};
+
+	assert_eq!(module_path_of::<Box<str>>(), Some("std::boxed"));
+	assert_eq!(module_path_of::<Rc<u8>>(), Some("std::rc"));
+	assert_eq!(module_path_of::<Arc<u8>>(), Some("std::sync"));
+	assert_eq!(module_path_of::<Vec<u8>>(), Some("std::vec"));
+	assert_eq!(module_path_of::<Option<u8>>(), Some("std::option"));
+	assert_eq!(module_path_of::<Result<u8, ()>>(), Some("std::result"));
+	assert_eq!(module_path_of::<Cow<str>>(), Some("std::borrow"));
+	assert_eq!(module_path_of::<String>(), Some("std::string"));
+
+	assert_eq!(module_path_of::<u32>(), None);
+	assert_eq!(module_path_of::<str>(), None);
+	assert_eq!(module_path_of::<()>(), None);
+	assert_eq!(module_path_of::<(u8, Box<str>)>(), None);
+	assert_eq!(module_path_of::<[Box<str>]>(), None);
+	assert_eq!(module_path_of::<&Box<str>>(), None);
+}
+
+#[test]
+fn hash_map_entry() {
+	use std::collections::hash_map::{Entry, OccupiedEntry, VacantEntry};
+
+	assert_type_name::<Entry<u32, String>>("Entry<u32, String>");
+	assert_type_name::<OccupiedEntry<u32, String>>("OccupiedEntry<u32, String>");
+	assert_type_name::<VacantEntry<u32, String>>("VacantEntry<u32, String>");
+	assert_type_name::<Option<Entry<(u8, u8), Vec<i32>>>>(
+		"Option<Entry<(u8, u8), Vec<i32>>>"
+	);
+	assert_eq!(
+		module_path_of::<Entry<u32, String>>(),
+		Some("std::collections::hash_map")
+	);
+}
+
+#[test]
+fn pin() {
+	use std::{pin::Pin, rc::Rc, sync::Arc};
+
+	assert_type_name::<Pin<Box<i32>>>("Pin<Box<i32>>");
+	assert_type_name::<Pin<Rc<u32>>>("Pin<Rc<u32>>");
+	assert_type_name::<Pin<Arc<u32>>>("Pin<Arc<u32>>");
+	assert_type_name::<Pin<Arc<str>>>("Pin<Arc<str>>");
+	assert_type_name::<Pin<&'static mut String>>("Pin<&mut String>");
+	assert_eq!(module_path_of::<Pin<Box<i32>>>(), Some("std::pin"));
+}
+
+#[test]
+fn pin_of_box_and_mut_ref() {
+	use std::pin::Pin;
+
+	assert_type_name::<Pin<&'static mut Box<i32>>>("Pin<&mut Box<i32>>");
+	assert_type_name::<Pin<Box<&'static mut [u8]>>>("Pin<Box<&mut [u8]>>");
+	assert_type_name::<Option<Pin<&'static mut Box<str>>>>("Option<Pin<&mut Box<str>>>");
+	assert_eq!(<Pin<Box<i32>>>::GENERIC_ARITY, 1);
+}
+
+#[test]
+fn weak() {
+	use std::{rc, sync};
+
+	assert_type_name::<sync::Weak<u32>>("Weak<u32>");
+	assert_type_name::<rc::Weak<u32>>("Weak<u32>");
+	assert_type_name::<sync::Weak<str>>("Weak<str>");
+	assert_type_name::<Option<sync::Weak<u32>>>("Option<Weak<u32>>");
+	assert_type_name::<Option<rc::Weak<[u8]>>>("Option<Weak<[u8]>>");
+	assert_eq!(module_path_of::<sync::Weak<u32>>(), Some("std::sync"));
+	assert_eq!(module_path_of::<rc::Weak<u32>>(), Some("std::rc"));
+}
+
+#[test]
+fn weak_of_downgraded() {
+	use crate::{qualified_type_name, type_name_of_val};
+	use std::{rc::Rc, sync::Arc};
+
+	let rc = Rc::new(42_i32);
+	let arc: Arc<str> = Arc::from("tyname");
+	assert_eq!(type_name_of_val(&Rc::downgrade(&rc)), "Weak<i32>");
+	assert_eq!(type_name_of_val(&Arc::downgrade(&arc)), "Weak<str>");
+	assert_eq!(qualified_type_name::<std::rc::Weak<i32>>(), "std::rc::Weak<i32>");
+	assert_eq!(qualified_type_name::<std::sync::Weak<str>>(), "std::sync::Weak<str>");
+}
+
+#[test]
+fn iter_adapters() {
+	use std::{
+		iter::{Skip, StepBy, Take},
+		vec::IntoIter,
+	};
+
+	assert_type_name::<IntoIter<u32>>("IntoIter<u32>");
+	assert_type_name::<StepBy<IntoIter<u32>>>("StepBy<IntoIter<u32>>");
+	assert_type_name::<Take<IntoIter<u32>>>("Take<IntoIter<u32>>");
+	assert_type_name::<Skip<IntoIter<u32>>>("Skip<IntoIter<u32>>");
+	assert_type_name::<Take<Skip<StepBy<IntoIter<String>>>>>(
+		"Take<Skip<StepBy<IntoIter<String>>>>"
+	);
+	assert_eq!(module_path_of::<Take<IntoIter<u32>>>(), Some("std::iter"));
+	assert_eq!(module_path_of::<IntoIter<u32>>(), Some("std::vec"));
+}
+
+#[test]
+fn iter_pair_adapters() {
+	use std::{
+		iter::{Chain, Zip},
+		vec::IntoIter,
+	};
+
+	assert_type_name::<Chain<IntoIter<u32>, IntoIter<u32>>>(
+		"Chain<IntoIter<u32>, IntoIter<u32>>"
+	);
+	assert_type_name::<Zip<IntoIter<u8>, IntoIter<String>>>(
+		"Zip<IntoIter<u8>, IntoIter<String>>"
+	);
+	assert_type_name::<Zip<Chain<IntoIter<u8>, IntoIter<u8>>, IntoIter<bool>>>(
+		"Zip<Chain<IntoIter<u8>, IntoIter<u8>>, IntoIter<bool>>"
+	);
+	assert_eq!(module_path_of::<Zip<IntoIter<u8>, IntoIter<u8>>>(), Some("std::iter"));
+}
+
+#[test]
+fn control_flow() {
+	use std::ops::ControlFlow;
+
+	assert_type_name::<ControlFlow<u32>>("ControlFlow<u32>");
+	assert_type_name::<ControlFlow<u32, ()>>("ControlFlow<u32>");
+	assert_type_name::<ControlFlow<u32, String>>("ControlFlow<u32, String>");
+	assert_type_name::<ControlFlow<(), ()>>("ControlFlow<()>");
+	assert_type_name::<ControlFlow<Vec<u8>, ((),)>>("ControlFlow<Vec<u8>, ((),)>");
+	assert_type_name::<ControlFlow<(), usize>>("ControlFlow<(), usize>");
+	assert_type_name::<Option<ControlFlow<String, usize>>>("Option<ControlFlow<String, usize>>");
+	assert_eq!(module_path_of::<ControlFlow<u32>>(), Some("std::ops"));
+}
+
+#[test]
+fn linked_list_iter() {
+	use std::collections::linked_list::{Iter, IterMut};
+
+	assert_type_name::<Iter<u32>>("linked_list::Iter<u32>");
+	assert_type_name::<IterMut<String>>("linked_list::IterMut<String>");
+	assert_type_name::<Option<Iter<Vec<u8>>>>("Option<linked_list::Iter<Vec<u8>>>");
+	assert_eq!(
+		module_path_of::<Iter<u32>>(),
+		Some("std::collections::linked_list")
+	);
+}
+
+#[test]
+fn btree_map_range() {
+	use std::collections::btree_map::Range;
+
+	assert_type_name::<Range<u32, String>>("btree_map::Range<u32, String>");
+	assert_type_name::<Vec<Range<(u8, i8), Vec<bool>>>>(
+		"Vec<btree_map::Range<(u8, i8), Vec<bool>>>"
+	);
+	assert_eq!(
+		module_path_of::<Range<u32, String>>(),
+		Some("std::collections::btree_map")
+	);
+}
+
+#[test]
+fn container_element() {
+	use std::{collections::VecDeque, rc::Rc};
+
+	assert_eq!(Vec::<u32>::element_type_name(), "u32");
+	assert_eq!(Box::<str>::element_type_name(), "str");
+	assert_eq!(Rc::<[u8]>::element_type_name(), "[u8]");
+	assert_eq!(Option::<Vec<bool>>::element_type_name(), "Vec<bool>");
+	assert_eq!(VecDeque::<(u8, i8)>::element_type_name(), "(u8, i8)");
+	assert_eq!(<[char]>::element_type_name(), "char");
+	assert_eq!(<[f32; 4]>::element_type_name(), "f32");
+}
+
+#[test]
+fn c_pointer_smartptrs() {
+	use std::{rc::Rc, sync::Arc};
+
+	let config = FormatConfig {
+		c_pointer_smartptrs: true,
+		..FormatConfig::default()
+	};
+	assert_type_name::<Box<u32>>("Box<u32>");
+	assert_type_name::<Arc<str>>("Arc<str>");
+	assert_eq!(type_name_with::<Box<u32>>(&config), "u32*");
+	assert_eq!(type_name_with::<Arc<str>>(&config), "str*");
+	assert_eq!(type_name_with::<Rc<[u8]>>(&config), "[u8]*");
+	assert_eq!(type_name_with::<Box<Box<i8>>>(&config), "i8**");
+	assert_eq!(type_name_with::<Vec<Box<bool>>>(&config), "Vec<bool*>");
+}
+
+#[test]
+fn box_allocator_elision() {
+	// The defaulted allocator parameter of `Box` is never written.
+	assert_type_name::<Box<u32>>("Box<u32>");
+	assert_type_name::<Box<[u8]>>("Box<[u8]>");
+	assert!(std::any::type_name::<Box<u32>>().ends_with(&type_name::<Box<u32>>()));
+}
+
+#[test]
+fn default_arg_elision() {
+	use std::{
+		collections::{hash_map::DefaultHasher, HashMap, HashSet},
+		hash::BuildHasherDefault,
+		ops::ControlFlow,
+		rc::Rc,
+		sync::Arc,
+	};
+	type Hasher = BuildHasherDefault<DefaultHasher>;
+
+	assert_type_name::<HashMap<u32, bool>>("HashMap<u32, bool>");
+	assert_type_name::<HashMap<u32, bool, Hasher>>("HashMap<u32, bool>");
+	assert_type_name::<HashSet<String>>("HashSet<String>");
+	assert_type_name::<HashSet<String, Hasher>>("HashSet<String>");
+	assert_type_name::<Box<u8>>("Box<u8>");
+	assert_type_name::<Rc<u8>>("Rc<u8>");
+	assert_type_name::<Arc<u8>>("Arc<u8>");
+	assert_type_name::<Vec<u8>>("Vec<u8>");
+	assert_type_name::<ControlFlow<u8>>("ControlFlow<u8>");
+	assert_type_name::<ControlFlow<u8, ()>>("ControlFlow<u8>");
+}
+
+/// A hasher that deliberately does not implement `TypeName`.
+#[derive(Default)]
+struct FixedState;
+
+impl std::hash::BuildHasher for FixedState {
+	type Hasher = std::collections::hash_map::DefaultHasher;
+
+	fn build_hasher(&self) -> Self::Hasher {
+		Default::default()
+	}
+}
+
+#[test]
+fn hash_map_custom_hasher() {
+	use std::collections::{HashMap, HashSet};
+
+	assert_type_name::<HashMap<String, u64>>("HashMap<String, u64>");
+	assert_type_name::<HashMap<String, u64, FixedState>>("HashMap<String, u64>");
+	assert_type_name::<HashSet<String, FixedState>>("HashSet<String>");
+	assert_eq!(
+		type_name::<HashMap<String, u64>>(),
+		type_name::<HashMap<String, u64, FixedState>>()
+	);
+}
+
+#[test]
+fn duration() {
+	use std::time::Duration;
+
+	assert_type_name::<Duration>("Duration");
+	assert_type_name::<Option<Duration>>("Option<Duration>");
+	assert_eq!(module_path_of::<Duration>(), Some("std::time"));
+}
+
+#[test]
+fn time() {
+	use std::time::{Duration, Instant, SystemTime};
+
+	assert_type_name::<Instant>("Instant");
+	assert_type_name::<SystemTime>("SystemTime");
+	assert_type_name::<Vec<Duration>>("Vec<Duration>");
+	assert_type_name::<(Instant, Option<SystemTime>)>("(Instant, Option<SystemTime>)");
+	assert_eq!(module_path_of::<Instant>(), Some("std::time"));
+}
+
+#[test]
+fn borrow_errors() {
+	use std::cell::{BorrowError, BorrowMutError};
+
+	assert_type_name::<BorrowError>("BorrowError");
+	assert_type_name::<BorrowMutError>("BorrowMutError");
+	assert_type_name::<Result<(), BorrowError>>("Result<(), BorrowError>");
+	assert_eq!(module_path_of::<BorrowMutError>(), Some("std::cell"));
+}
+
+#[test]
+fn io_slices() {
+	use std::io::{IoSlice, IoSliceMut};
+
+	assert_type_name::<IoSlice<'static>>("IoSlice");
+	assert_type_name::<IoSliceMut<'static>>("IoSliceMut");
+	assert_type_name::<&[IoSlice<'static>]>("&[IoSlice]");
+	assert_type_name::<Vec<IoSliceMut<'static>>>("Vec<IoSliceMut>");
+	assert_eq!(module_path_of::<IoSlice<'static>>(), Some("std::io"));
+}
+
+#[test]
+fn orderings() {
+	use std::{cmp, sync::atomic};
+
+	assert_type_name::<atomic::Ordering>("atomic::Ordering");
+	assert_type_name::<cmp::Ordering>("Ordering");
+	assert_type_name::<(cmp::Ordering, atomic::Ordering)>("(Ordering, atomic::Ordering)");
+	assert_eq!(module_path_of::<atomic::Ordering>(), Some("std::sync::atomic"));
+	assert_eq!(module_path_of::<cmp::Ordering>(), Some("std::cmp"));
+}
+
+#[test]
+fn orderings_in_collections() {
+	use crate::qualified_type_name;
+	use core::{cmp::Ordering, sync::atomic};
+
+	assert_type_name::<Vec<Ordering>>("Vec<Ordering>");
+	assert_type_name::<Option<Vec<atomic::Ordering>>>("Option<Vec<atomic::Ordering>>");
+	assert_eq!(qualified_type_name::<Vec<Ordering>>(), "std::vec::Vec<std::cmp::Ordering>");
+}
+
+#[test]
+fn formatter() {
+	use std::fmt::Formatter;
+
+	assert_type_name::<Formatter<'static>>("Formatter");
+	assert_type_name::<&mut Formatter<'static>>("&mut Formatter");
+	assert_type_name::<Option<&mut Formatter<'static>>>("Option<&mut Formatter>");
+}
+
+mod vecdeque_composition {
+	use super::assert_type_name;
+	use std::{
+		collections::{HashMap, VecDeque},
+		sync::{Arc, Mutex},
+	};
+
+	#[test]
+	fn with_map() {
+		assert_type_name::<VecDeque<HashMap<u32, Vec<u8>>>>(
+			"VecDeque<HashMap<u32, Vec<u8>>>"
+		);
+		assert_type_name::<HashMap<String, VecDeque<u8>>>(
+			"HashMap<String, VecDeque<u8>>"
+		);
+	}
+
+	#[test]
+	fn with_sync() {
+		assert_type_name::<Arc<Mutex<VecDeque<String>>>>(
+			"Arc<Mutex<VecDeque<String>>>"
+		);
+		assert_type_name::<VecDeque<Arc<Mutex<[u8]>>>>(
+			"VecDeque<Arc<Mutex<[u8]>>>"
+		);
+	}
+}
+
+#[test]
+fn proto() {
+	use crate::proto_type_name;
+
+	assert_eq!(proto_type_name::<u32>(), "uint32");
+	assert_eq!(proto_type_name::<i64>(), "int64");
+	assert_eq!(proto_type_name::<f64>(), "double");
+	assert_eq!(proto_type_name::<f32>(), "float");
+	assert_eq!(proto_type_name::<bool>(), "bool");
+	assert_eq!(proto_type_name::<String>(), "string");
+	assert_eq!(proto_type_name::<str>(), "string");
+	assert_eq!(proto_type_name::<Vec<u8>>(), "bytes");
+	assert_eq!(proto_type_name::<Vec<u32>>(), "repeated uint32");
+	assert_eq!(proto_type_name::<Vec<String>>(), "repeated string");
+}
+
+#[test]
+fn graphql() {
+	use crate::graphql_type_name;
+
+	assert_eq!(graphql_type_name::<i32>(), "Int!");
+	assert_eq!(graphql_type_name::<Option<i32>>(), "Int");
+	assert_eq!(graphql_type_name::<f64>(), "Float!");
+	assert_eq!(graphql_type_name::<bool>(), "Boolean!");
+	assert_eq!(graphql_type_name::<String>(), "String!");
+	assert_eq!(graphql_type_name::<Vec<String>>(), "[String!]!");
+	assert_eq!(graphql_type_name::<Vec<Option<String>>>(), "[String]!");
+	assert_eq!(graphql_type_name::<Option<Vec<Vec<u8>>>>(), "[[Int!]!]");
+}
+
+#[test]
+fn cow_str() {
+	use std::borrow::Cow;
+
+	assert_type_name::<Cow<str>>("Cow<str>");
+	assert_type_name::<Cow<'static, str>>("Cow<str>");
+	assert_type_name::<Option<Cow<str>>>("Option<Cow<str>>");
+}
+
+#[test]
+fn cow_vec() {
+	use std::borrow::Cow;
+
+	assert_type_name::<Cow<Vec<u8>>>("Cow<Vec<u8>>");
+	assert_type_name::<Cow<'static, Vec<String>>>("Cow<Vec<String>>");
+	assert_type_name::<Option<Cow<Vec<(u8, bool)>>>>("Option<Cow<Vec<(u8, bool)>>>");
+}
+
+#[test]
+fn cow_slices() {
+	use std::borrow::Cow;
+
+	assert_type_name::<Cow<str>>("Cow<str>");
+	assert_type_name::<Cow<[u8]>>("Cow<[u8]>");
+	assert_type_name::<Cow<[i32]>>("Cow<[i32]>");
+	assert_type_name::<Cow<'static, [String]>>("Cow<[String]>");
+	assert_type_name::<Vec<Cow<[(u8, char)]>>>("Vec<Cow<[(u8, char)]>>");
+}
+
+#[test]
+fn cow_qualified() {
+	use crate::{qualified_type_name, std_compat_type_name};
+	use std::borrow::Cow;
+
+	assert_eq!(qualified_type_name::<Cow<str>>(), "std::borrow::Cow<str>");
+	assert_eq!(qualified_type_name::<Cow<[u8]>>(), "std::borrow::Cow<[u8]>");
+	assert_eq!(std_compat_type_name::<Cow<str>>(), "alloc::borrow::Cow<str>");
+	assert_eq!(std_compat_type_name::<Cow<[u8]>>(), "alloc::borrow::Cow<[u8]>");
+}
+
+#[test]
+fn string_variants() {
+	use std::{borrow::Cow, rc::Rc, sync::Arc};
+
+	assert_type_name::<String>("String");
+	assert_type_name::<&str>("&str");
+	assert_type_name::<Box<str>>("Box<str>");
+	assert_type_name::<Rc<str>>("Rc<str>");
+	assert_type_name::<Arc<str>>("Arc<str>");
+	assert_type_name::<Cow<str>>("Cow<str>");
+}
+
+#[test]
+fn try_type_name() {
+	use std::fmt::{Error, Write};
+
+	/// Deliberately failing impl.
+	struct Failing;
+
+	impl TypeName for Failing {
+		fn write_type_name<W>(w: &mut W) -> crate::FmtResult where W: Write {
+			w.write_str("Failing")?;
+			Err(Error)
+		}
+	}
+
+	assert_eq!(crate::try_type_name::<Vec<u8>>(), Ok(String::from("Vec<u8>")));
+	assert_eq!(crate::try_type_name::<Failing>(), Err(Error));
+	assert_eq!(crate::try_type_name::<Option<Failing>>(), Err(Error));
+}
+
+#[test]
+fn of_val() {
+	use crate::type_name_of_val;
+
+	let vec: Vec<u8> = vec![1, 2, 3];
+	assert_eq!(type_name_of_val(&vec), type_name::<Vec<u8>>());
+	assert_eq!(type_name_of_val(&vec[..]), type_name::<[u8]>());
+	assert_eq!(type_name_of_val(&&vec[..]), "&[u8]");
+	assert_eq!(type_name_of_val("str"), "str");
+	assert_eq!(type_name_of_val(&(1_i32, true)), type_name::<(i32, bool)>());
+}
+
+#[test]
+fn fnv1a() {
+	use crate::hash::Fnv1aWriter;
+	use std::fmt::Write;
+
+	let hash = |input: &str| {
+		let mut hasher = Fnv1aWriter::new();
+		hasher.write_str(input).unwrap();
+		hasher.finish()
+	};
+	// Test vectors of the FNV-1a specification.
+	assert_eq!(hash(""), 0xcbf2_9ce4_8422_2325);
+	assert_eq!(hash("a"), 0xaf63_dc4c_8601_ec8c);
+	assert_eq!(hash("foobar"), 0x8594_4171_f739_67e8);
+}
+
+#[test]
+fn type_name_hash() {
+	use crate::{hash::Fnv1aWriter, type_name_hash};
+	use std::fmt::Write;
+
+	assert_eq!(type_name_hash::<Vec<u8>>(), type_name_hash::<Vec<u8>>());
+	assert_ne!(type_name_hash::<Vec<u8>>(), type_name_hash::<Vec<i8>>());
+	assert_ne!(type_name_hash::<(u8, u16)>(), type_name_hash::<(u16, u8)>());
+	// The hash is the FNV-1a hash of the written name.
+	let mut hasher = Fnv1aWriter::new();
+	hasher.write_str("Option<bool>").unwrap();
+	assert_eq!(type_name_hash::<Option<bool>>(), hasher.finish());
+	// Pinned so that changes of the hash are noticed.
+	assert_eq!(type_name_hash::<u8>(), 0x08c4_8207_b567_53d8);
+}
+
+#[test]
+fn reuse() {
+	use crate::type_name_reuse;
+
+	let mut buffer = String::with_capacity(64);
+	let capacity = buffer.capacity();
+	assert_eq!(type_name_reuse::<u32>(&mut buffer), "u32");
+	assert_eq!(type_name_reuse::<Vec<u8>>(&mut buffer), "Vec<u8>");
+	assert_eq!(type_name_reuse::<()>(&mut buffer), "()");
+	assert_eq!(buffer.capacity(), capacity);
+}
+
+#[test]
+fn html_escaped() {
+	use crate::html_escaped_type_name;
+
+	assert_eq!(html_escaped_type_name::<Vec<u32>>(), "Vec&lt;u32&gt;");
+	assert_eq!(html_escaped_type_name::<&u8>(), "&amp;u8");
+	assert_eq!(
+		html_escaped_type_name::<Option<&mut [u8]>>(),
+		"Option&lt;&amp;mut [u8]&gt;"
+	);
+	assert_eq!(html_escaped_type_name::<fn() -> u8>(), "fn() -&gt; u8");
+	assert_eq!(html_escaped_type_name::<u8>(), "u8");
+}
+
+#[test]
+fn bounded() {
+	use crate::{SizeExceeded, type_name_bounded};
+
+	assert_eq!(type_name_bounded::<u8>(2), Ok(String::from("u8")));
+	assert_eq!(type_name_bounded::<Vec<u8>>(100), Ok(String::from("Vec<u8>")));
+	assert_eq!(type_name_bounded::<u8>(1), Err(SizeExceeded { max_bytes: 1 }));
+	assert_eq!(
+		type_name_bounded::<Vec<Result<Box<[u8]>, String>>>(8),
+		Err(SizeExceeded { max_bytes: 8 })
+	);
+	assert_eq!(type_name_bounded::<()>(0), Err(SizeExceeded { max_bytes: 0 }));
+	for max_bytes in 0..40 {
+		if let Ok(name) = type_name_bounded::<Vec<Result<Box<[u8]>, String>>>(max_bytes) {
+			assert!(name.capacity() <= max_bytes);
+		}
+	}
+}
+
+/// Impl that fails on its own instead of exceeding a budget.
+struct Failing;
+
+impl TypeName for Failing {
+	fn write_type_name<W>(_: &mut W) -> crate::FmtResult where W: core::fmt::Write {
+		Err(core::fmt::Error)
+	}
+}
+
+#[test]
+#[should_panic(expected = "[tyname::type_name_bounded] Encountered error while writing type name")]
+fn bounded_impl_error() {
+	let _ = crate::type_name_bounded::<Failing>(100);
+}
+
+mod wrapping_arrays {
+	use super::assert_type_name;
+	use std::num::{Saturating, Wrapping};
+
+	#[test]
+	fn wrappers() {
+		assert_type_name::<Wrapping<i32>>("Wrapping<i32>");
+		assert_type_name::<Saturating<u8>>("Saturating<u8>");
+	}
+
+	#[test]
+	fn in_arrays() {
+		assert_type_name::<[Saturating<u8>; 4]>("[Saturating<u8>; 4]");
+		assert_type_name::<[Wrapping<u64>; 32]>("[Wrapping<u64>; 32]");
+		assert_type_name::<[Saturating<i16>]>("[Saturating<i16>]");
+		assert_type_name::<[Saturating<u16>; 8]>("[Saturating<u16>; 8]");
+		assert_type_name::<[[Saturating<u16>; 8]; 2]>("[[Saturating<u16>; 8]; 2]");
+	}
+
+	#[test]
+	fn in_collections() {
+		assert_type_name::<Vec<Wrapping<i32>>>("Vec<Wrapping<i32>>");
+		assert_type_name::<Option<[Wrapping<u8>; 2]>>("Option<[Wrapping<u8>; 2]>");
+	}
+}
+
+struct Pair<A, B>(A, B);
+
+crate::impl_type_name_generic2!(Pair, "Pair");
+
+#[test]
+fn generic2_macro() {
+	assert_type_name::<Pair<u32, String>>("Pair<u32, String>");
+	assert_type_name::<Vec<Pair<u8, Pair<i8, ()>>>>("Vec<Pair<u8, Pair<i8, ()>>>");
+	assert_eq!(module_path_of::<Pair<u32, u32>>(), Some("tyname::tests"));
+	assert_eq!(<Pair<u32, u32>>::GENERIC_ARITY, 2);
+}
+
+struct Meters(i64);
+
+crate::impl_type_name!(Meters => "Meters");
+
+#[test]
+fn impl_type_name_macro() {
+	let _ = Meters(42).0;
+	assert_type_name::<Meters>("Meters");
+	assert_type_name::<Option<(Meters, Meters)>>("Option<(Meters, Meters)>");
+	assert_eq!(module_path_of::<Meters>(), Some("tyname::tests"));
+	assert_eq!(crate::static_type_name::<Meters>(), Some("Meters"));
+}
+
+/// Stand-in for a crate whose types do not implement `TypeName`.
+mod foreign {
+	pub struct Handle(pub u32);
+}
+
+crate::name_foreign_type!(Handle, foreign::Handle, "ForeignHandle");
+
+#[test]
+fn foreign_type() {
+	let handle = Handle::from(foreign::Handle(7));
+	assert_eq!((handle.0).0, 7);
+	assert_type_name::<Handle>("ForeignHandle");
+	assert_type_name::<Option<Vec<Handle>>>("Option<Vec<ForeignHandle>>");
+	assert_eq!(module_path_of::<Handle>(), Some("tyname::tests"));
+}
+
+crate::impl_type_name_dyn!(dyn std::fmt::Debug, "dyn Debug");
+crate::impl_type_name_dyn!(dyn std::fmt::Debug + Send + Sync, "dyn Debug + Send + Sync");
+
+#[test]
+fn dyn_any() {
+	use std::{any::Any, rc::Rc};
+
+	assert_type_name::<Box<dyn Any>>("Box<dyn Any>");
+	assert_type_name::<Rc<dyn Any>>("Rc<dyn Any>");
+	assert_type_name::<Box<dyn Any + Send>>("Box<dyn Any + Send>");
+	assert_type_name::<Box<dyn Any + Send + Sync>>("Box<dyn Any + Send + Sync>");
+	assert_type_name::<&'static dyn Any>("&dyn Any");
+}
+
+#[test]
+fn dyn_error() {
+	use std::error::Error;
+
+	assert_type_name::<Box<dyn Error>>("Box<dyn Error>");
+	assert_type_name::<Box<dyn Error + Send + Sync>>("Box<dyn Error + Send + Sync>");
+	assert_type_name::<Result<(), Box<dyn Error + Send>>>("Result<(), Box<dyn Error + Send>>");
+}
+
+trait Plugin {}
+
+crate::impl_type_name_dyn!(dyn Plugin => "dyn Plugin");
+
+#[test]
+fn dyn_local_trait() {
+	assert_type_name::<Box<dyn Plugin>>("Box<dyn Plugin>");
+	assert_type_name::<Vec<Box<dyn Plugin>>>("Vec<Box<dyn Plugin>>");
+	assert_type_name::<&'static dyn Plugin>("&dyn Plugin");
+}
+
+#[test]
+fn dyn_future() {
+	use std::{future::Future, pin::Pin};
+
+	assert_type_name::<dyn Future<Output = ()>>("dyn Future<Output = ()>");
+	assert_type_name::<Pin<Box<dyn Future<Output = ()>>>>("Pin<Box<dyn Future<Output = ()>>>");
+	assert_type_name::<Pin<Box<dyn Future<Output = Option<u32>> + Send>>>(
+		"Pin<Box<dyn Future<Output = Option<u32>> + Send>>"
+	);
+	assert_type_name::<Pin<&mut (dyn Future<Output = bool> + Send + Sync)>>(
+		"Pin<&mut dyn Future<Output = bool> + Send + Sync>"
+	);
+}
+
+#[test]
+fn legacy_dyn() {
+	use std::fmt::Debug;
+
+	let legacy = FormatConfig {
+		legacy_dyn: true,
+		..FormatConfig::default()
+	};
+	assert_type_name::<Box<dyn Debug>>("Box<dyn Debug>");
+	assert_eq!(type_name_with::<Box<dyn Debug>>(&legacy), "Box<Debug>");
+	assert_eq!(
+		type_name_with::<Box<dyn Debug + Send + Sync>>(&FormatConfig::default()),
+		"Box<dyn Debug + Send + Sync>"
+	);
+	assert_eq!(
+		type_name_with::<Box<dyn Debug + Send + Sync>>(&legacy),
+		"Box<Debug + Send + Sync>"
+	);
+	assert_eq!(type_name_with::<&'static dyn Debug>(&legacy), "&Debug");
+}
+
+#[test]
+fn type_name_tree() {
+	let u32_ = || TypeNameTree::named("u32", vec![]);
+	let u8_ = || TypeNameTree::named("u8", vec![]);
+
+	let vec = TypeNameTree::named("Vec", vec![u32_()]);
+	assert_eq!(vec.render(), "Vec<u32>");
+	assert_eq!(vec.render(), type_name::<Vec<u32>>());
+
+	let array_ref = TypeNameTree::reference(true, TypeNameTree::array(u8_(), 4));
+	assert_eq!(array_ref.render(), "&mut [u8; 4]");
+	assert_eq!(array_ref.render(), type_name::<&mut [u8; 4]>());
+
+	let nested = TypeNameTree::named("Result", vec![
+		TypeNameTree::tuple(vec![u8_()]),
+		TypeNameTree::pointer(false, TypeNameTree::slice(u32_())),
+	]);
+	assert_eq!(nested.to_string(), "Result<(u8,), *const [u32]>");
+	assert_eq!(nested.render(), type_name::<Result<(u8,), *const [u32]>>());
+	assert_eq!(TypeNameTree::tuple(vec![]).render(), "()");
+}
+
+#[test]
+fn tokens() {
+	use crate::{type_name_tokens, type_name_tokens_into, OwnedToken, Token::*, Tokens};
+
+	assert_eq!(
+		type_name_tokens::<Vec<u32>>().collect::<Vec<_>>(),
+		[
+			OwnedToken::Ident(String::from("Vec")), OwnedToken::Punct(Open),
+			OwnedToken::Ident(String::from("u32")), OwnedToken::Punct(Close),
+		]
+	);
+	let mut tokens = type_name_tokens::<Option<[u8; 4]>>();
+	assert_eq!(tokens.next(), Some(OwnedToken::Ident(String::from("Option"))));
+	assert_eq!(tokens.clone().count(), 7);
+	assert_eq!(tokens.last(), Some(OwnedToken::Punct(Close)));
+	let mut buffer = String::new();
+	assert_eq!(
+		type_name_tokens_into::<Vec<u32>>(&mut buffer).collect::<Vec<_>>(),
+		[Ident("Vec"), Open, Ident("u32"), Close]
+	);
+	assert_eq!(buffer, "Vec<u32>");
+	assert_eq!(
+		type_name_tokens_into::<&mut [(u8, bool); 4]>(&mut buffer).collect::<Vec<_>>(),
+		[
+			Ref, Ident("mut"), OpenBracket, OpenParen, Ident("u8"), Comma,
+			Ident("bool"), CloseParen, Semicolon, Ident("4"), CloseBracket,
+		]
+	);
+	assert_eq!(
+		type_name_tokens_into::<fn(*const str) -> Option<u8>>(&mut buffer).collect::<Vec<_>>(),
+		[
+			Ident("fn"), OpenParen, Ptr, Ident("const"), Ident("str"), CloseParen,
+			Arrow, Ident("Option"), Open, Ident("u8"), Close,
+		]
+	);
+	assert_eq!(
+		Tokens::new("btree_map::Range<dyn Debug + Send>").collect::<Vec<_>>(),
+		[Ident("btree_map::Range"), Open, Ident("dyn"), Ident("Debug"), Plus, Ident("Send"), Close]
+	);
+	assert_eq!(Tokens::new("").next(), None);
+}
+
+#[cfg(debug_assertions)]
+mod bracket_validation {
+	use super::*;
+	use std::fmt::Write;
+
+	/// Deliberately broken impl that forgets its closing bracket.
+	struct Unclosed;
+
+	impl TypeName for Unclosed {
+		fn write_type_name<W>(w: &mut W) -> crate::FmtResult where W: Write {
+			w.write_str("Unclosed<u32")
+		}
+	}
+
+	/// Deliberately broken impl that closes the wrong bracket.
+	struct Mismatched;
+
+	impl TypeName for Mismatched {
+		fn write_type_name<W>(w: &mut W) -> crate::FmtResult where W: Write {
+			w.write_str("Mismatched<[u32>]")
+		}
+	}
+
+	#[test]
+	#[should_panic(expected = "unbalanced brackets in type name `Vec<Unclosed<u32>`")]
+	fn unclosed() {
+		type_name::<Vec<Unclosed>>();
+	}
+
+	#[test]
+	#[should_panic(expected = "unbalanced brackets in type name `Mismatched<[u32>]`")]
+	fn mismatched() {
+		type_name::<Mismatched>();
+	}
+
+	#[test]
+	fn return_arrow_is_not_a_bracket() {
+		assert_type_name::<fn(u8) -> Vec<u8>>("fn(u8) -> Vec<u8>");
+	}
+
+	/// Impl writing `char` const generic arguments as literals.
+	struct Delimiters;
+
+	const DELIMITERS: &str = r"Delimiters<'>', '(', '\'', '\\', '['>";
+
+	impl TypeName for Delimiters {
+		fn write_type_name<W>(w: &mut W) -> crate::FmtResult where W: Write {
+			w.write_str(DELIMITERS)
+		}
+
+		fn type_name_len_hint() -> usize {
+			DELIMITERS.len()
+		}
+	}
+
+	#[test]
+	fn char_literals_are_not_brackets() {
+		assert_type_name::<Vec<Delimiters>>(r"Vec<Delimiters<'>', '(', '\'', '\\', '['>>");
+	}
+}
+
+mod hashset_composition {
+	use super::assert_type_name;
+	use std::{
+		collections::{
+			hash_map::{DefaultHasher, RandomState},
+			HashMap, HashSet,
+		},
+		hash::BuildHasherDefault,
+	};
+
+	type Hasher = BuildHasherDefault<DefaultHasher>;
+
+	#[test]
+	fn vec_of_hashset() {
+		assert_type_name::<Vec<HashSet<u32>>>("Vec<HashSet<u32>>");
+		assert_type_name::<Vec<HashSet<u32, Hasher>>>("Vec<HashSet<u32>>");
+	}
+
+	#[test]
+	fn hashmap_of_hashset() {
+		assert_type_name::<HashMap<String, HashSet<u32>>>("HashMap<String, HashSet<u32>>");
+		assert_type_name::<HashMap<String, HashSet<u32, Hasher>, RandomState>>(
+			"HashMap<String, HashSet<u32>>"
+		);
+	}
+
+	#[test]
+	fn hashset_of_composites() {
+		assert_type_name::<HashSet<(u8, Vec<char>)>>("HashSet<(u8, Vec<char>)>");
+		assert_type_name::<Option<HashSet<&'static str>>>("Option<HashSet<&str>>");
+	}
+}
+
+mod range_variants {
+	use super::{assert_type_name, module_path_of};
+	use std::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+	#[test]
+	fn distinct_names() {
+		assert_type_name::<Range<u32>>("Range<u32>");
+		assert_type_name::<RangeInclusive<u32>>("RangeInclusive<u32>");
+		assert_type_name::<RangeFrom<u32>>("RangeFrom<u32>");
+		assert_type_name::<RangeTo<u32>>("RangeTo<u32>");
+		assert_type_name::<RangeToInclusive<u32>>("RangeToInclusive<u32>");
+		assert_type_name::<RangeFull>("RangeFull");
+	}
+
+	#[test]
+	fn composition() {
+		assert_type_name::<Vec<RangeInclusive<u8>>>("Vec<RangeInclusive<u8>>");
+		assert_type_name::<Option<Range<usize>>>("Option<Range<usize>>");
+		assert_type_name::<Vec<(RangeTo<i8>, RangeFrom<i8>)>>("Vec<(RangeTo<i8>, RangeFrom<i8>)>");
+	}
+
+	#[test]
+	fn of_range_expressions() {
+		use crate::type_name_of_val;
+
+		assert_eq!(type_name_of_val(&(0..10)), "Range<i32>");
+		assert_eq!(type_name_of_val(&(0_u8..=9)), "RangeInclusive<u8>");
+		assert_eq!(type_name_of_val(&(1_i64..)), "RangeFrom<i64>");
+		assert_eq!(type_name_of_val(&(..'z')), "RangeTo<char>");
+		assert_eq!(type_name_of_val(&(..=2.5_f32)), "RangeToInclusive<f32>");
+		assert_eq!(type_name_of_val(&(..)), "RangeFull");
+	}
+
+	#[test]
+	fn not_btree_map_range() {
+		use std::collections::btree_map;
+
+		assert_type_name::<btree_map::Range<u32, u32>>("btree_map::Range<u32, u32>");
+		assert_eq!(module_path_of::<Range<u32>>(), Some("std::ops"));
+	}
+
+	#[test]
+	fn inclusive_index_sets() {
+		assert_type_name::<RangeInclusive<usize>>("RangeInclusive<usize>");
+		assert_type_name::<Vec<RangeInclusive<u32>>>("Vec<RangeInclusive<u32>>");
+		assert_type_name::<Vec<Vec<RangeInclusive<usize>>>>("Vec<Vec<RangeInclusive<usize>>>");
+		assert_type_name::<Option<Vec<RangeInclusive<u64>>>>("Option<Vec<RangeInclusive<u64>>>");
+	}
+}
+
+/// Pins the exact output for a curated set of representative types.
+///
+/// Any change of the output format has to update this table deliberately.
+/// A hand-written checker for the subset of the Rust type grammar that
+/// type names are written in, e.g. references, tuples, arrays, function
+/// pointers, paths with generic arguments and `dyn` trait objects.
+mod type_grammar {
+	#[derive(Debug, Clone, PartialEq)]
+	enum Token {
+		Ident(String),
+		Lifetime,
+		Literal,
+		Punct(&'static str),
+	}
+
+	fn tokenize(name: &str) -> Result<Vec<Token>, String> {
+		const PUNCTS: &[&str] = &[
+			"::", "->", "<", ">", "(", ")", "[", "]", "{", "}",
+			",", ";", "&", "*", "+", "?", "=", "!", "-",
+		];
+		let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+		let mut tokens = Vec::new();
+		let mut rest = name;
+		while let Some(c) = rest.chars().next() {
+			if c == ' ' {
+				rest = &rest[1..];
+			} else if c == '\'' {
+				let mut chars = rest[1..].chars();
+				let (len, token) = match (chars.next(), chars.next()) {
+					(Some('\\'), Some(escaped)) => {
+						// Note: The escaped char may be a quote itself.
+						let start = 2 + escaped.len_utf8();
+						let end = rest[start..]
+							.find('\'')
+							.ok_or_else(|| format!("unclosed char literal in `{}`", name))?;
+						(start + end + 1, Token::Literal)
+					}
+					(Some(c), Some('\'')) => (2 + c.len_utf8(), Token::Literal),
+					_ => (1 + rest[1..].find(|c| !is_ident(c)).unwrap_or(rest.len() - 1), Token::Lifetime),
+				};
+				tokens.push(token);
+				rest = &rest[len..];
+			} else if c == '"' {
+				let end = rest[1..].find('"').ok_or_else(|| format!("unclosed string in `{}`", name))?;
+				tokens.push(Token::Literal);
+				rest = &rest[end + 2..];
+			} else if is_ident(c) {
+				let end = rest.find(|c| !is_ident(c)).unwrap_or(rest.len());
+				tokens.push(match c {
+					'0'..='9' => Token::Literal,
+					_ => Token::Ident(String::from(&rest[..end])),
+				});
+				rest = &rest[end..];
+			} else {
+				let punct = PUNCTS
+					.iter()
+					.find(|punct| rest.starts_with(**punct))
+					.ok_or_else(|| format!("unexpected `{}` in `{}`", c, name))?;
+				tokens.push(Token::Punct(punct));
+				rest = &rest[punct.len()..];
+			}
+		}
+		Ok(tokens)
+	}
+
+	/// Keywords that cannot be path segments.
+	const KEYWORDS: &[&str] = &["as", "const", "dyn", "extern", "fn", "for", "impl", "mut", "unsafe"];
+
+	struct Parser {
+		tokens: Vec<Token>,
+		pos: usize,
+	}
+
+	type Parsed = Result<(), String>;
+
+	impl Parser {
+		fn peek(&self) -> Option<&Token> {
+			self.tokens.get(self.pos)
+		}
+
+		fn peek_is(&self, punct: &str) -> bool {
+			matches!(self.peek(), Some(Token::Punct(p)) if *p == punct)
+		}
+
+		fn peek_ident(&self, ident: &str) -> bool {
+			matches!(self.peek(), Some(Token::Ident(i)) if i == ident)
+		}
+
+		fn eat(&mut self, punct: &str) -> bool {
+			let found = self.peek_is(punct);
+			self.pos += found as usize;
+			found
+		}
+
+		fn eat_ident(&mut self, ident: &str) -> bool {
+			let found = self.peek_ident(ident);
+			self.pos += found as usize;
+			found
+		}
+
+		fn eat_token(&mut self, token: &Token) -> bool {
+			let found = self.peek() == Some(token);
+			self.pos += found as usize;
+			found
+		}
+
+		fn expect(&mut self, punct: &str) -> Parsed {
+			if self.eat(punct) {
+				return Ok(())
+			}
+			Err(format!("expected `{}` but found {:?}", punct, self.peek()))
+		}
+
+		/// `Type`, i.e. a type that may be a trait object with multiple bounds.
+		fn ty(&mut self) -> Parsed {
+			if self.eat_ident("dyn") || self.eat_ident("impl") {
+				self.bound()?;
+				while self.eat("+") {
+					self.bound()?;
+				}
+				return Ok(())
+			}
+			self.ty_no_bounds()
+		}
+
+		/// `TypeNoBounds` as found behind references, pointers and `->`.
+		fn ty_no_bounds(&mut self) -> Parsed {
+			if self.eat("(") {
+				while !self.eat(")") {
+					self.ty()?;
+					if !self.eat(",") {
+						return self.expect(")")
+					}
+				}
+				Ok(())
+			} else if self.eat("!") || self.eat_ident("_") {
+				Ok(())
+			} else if self.eat("&") {
+				self.eat_token(&Token::Lifetime);
+				self.eat_ident("mut");
+				self.ty_no_bounds()
+			} else if self.eat("*") {
+				if !self.eat_ident("const") && !self.eat_ident("mut") {
+					return Err(String::from("expected `const` or `mut` after `*`"))
+				}
+				self.ty_no_bounds()
+			} else if self.eat("[") {
+				self.ty()?;
+				if self.eat(";") {
+					self.expr()?;
+				}
+				self.expect("]")
+			} else if self.eat_ident("dyn") || self.eat_ident("impl") {
+				self.bound()
+			} else if self.peek_ident("unsafe") || self.peek_ident("extern") || self.peek_ident("fn") {
+				self.fn_ptr()
+			} else {
+				self.path(false)
+			}
+		}
+
+		/// A function pointer such as `unsafe extern "C" fn(u8) -> u8`.
+		fn fn_ptr(&mut self) -> Parsed {
+			self.eat_ident("unsafe");
+			if self.eat_ident("extern") {
+				self.eat_token(&Token::Literal);
+			}
+			if !self.eat_ident("fn") {
+				return Err(format!("expected `fn` but found {:?}", self.peek()))
+			}
+			self.fn_args()
+		}
+
+		/// The parenthesized parameters and return type of functions and `Fn` bounds.
+		fn fn_args(&mut self) -> Parsed {
+			self.expect("(")?;
+			while !self.eat(")") {
+				self.ty()?;
+				if !self.eat(",") {
+					self.expect(")")?;
+					break
+				}
+			}
+			if self.eat("->") {
+				self.ty_no_bounds()?;
+			}
+			Ok(())
+		}
+
+		/// A trait or lifetime bound of a trait object.
+		fn bound(&mut self) -> Parsed {
+			if self.eat_token(&Token::Lifetime) {
+				return Ok(())
+			}
+			self.eat("?");
+			self.path(true)
+		}
+
+		/// A path with generic arguments, e.g. `std::vec::Vec<u8>`.
+		///
+		/// Only bounds may use the parenthesized arguments of `Fn(u8) -> u8`.
+		fn path(&mut self, bound: bool) -> Parsed {
+			self.eat("::");
+			loop {
+				match self.tokens.get(self.pos) {
+					Some(Token::Ident(ident)) if !KEYWORDS.contains(&ident.as_str()) => self.pos += 1,
+					token => return Err(format!("expected path segment but found {:?}", token)),
+				}
+				let turbofish = self.eat("::");
+				if self.peek_is("<") {
+					self.generic_args()?;
+				} else if bound && self.peek_is("(") {
+					return self.fn_args()
+				} else if turbofish {
+					continue
+				}
+				if !self.eat("::") {
+					return Ok(())
+				}
+			}
+		}
+
+		fn generic_args(&mut self) -> Parsed {
+			self.expect("<")?;
+			while !self.eat(">") {
+				let binding = matches!(self.peek(), Some(Token::Ident(_)))
+					&& self.tokens.get(self.pos + 1) == Some(&Token::Punct("="));
+				let expr = matches!(
+					self.peek(),
+					Some(Token::Literal) | Some(Token::Punct("-")) | Some(Token::Punct("{"))
+				);
+				if binding {
+					self.pos += 2;
+					self.ty()?;
+				} else if expr {
+					self.expr()?;
+				} else if !self.eat_token(&Token::Lifetime) {
+					self.ty()?;
+				}
+				if !self.eat(",") {
+					return self.expect(">")
+				}
+			}
+			Ok(())
+		}
+
+		/// The array lengths and const generic arguments, e.g. `4`, `-1` or `{ N }`.
+		fn expr(&mut self) -> Parsed {
+			if self.eat("{") {
+				let mut depth = 1;
+				while depth > 0 {
+					depth += self.eat("{") as usize;
+					if self.eat("}") {
+						depth -= 1;
+					} else if self.pos >= self.tokens.len() {
+						return Err(String::from("unclosed block"))
+					} else {
+						self.pos += 1;
+					}
+				}
+				return Ok(())
+			}
+			self.eat("-");
+			if self.eat_token(&Token::Literal) {
+				return Ok(())
+			}
+			self.path(false)
+		}
+	}
+
+	/// Parses the given name as a Rust type.
+	pub fn parse(name: &str) -> Parsed {
+		let mut parser = Parser {
+			tokens: tokenize(name)?,
+			pos: 0,
+		};
+		parser.ty()?;
+		match parser.peek() {
+			None => Ok(()),
+			Some(token) => Err(format!("unexpected {:?} after type in `{}`", token, name)),
+		}
+	}
+
+	/// Asserts that the given name parses as a Rust type.
+	pub fn assert_parses(name: &str) {
+		if let Err(error) = parse(name) {
+			panic!("`{}` does not parse as Rust type: {}", name, error)
+		}
+	}
+
+	#[test]
+	fn accepts_types() {
+		for name in [
+			"u8", "()", "(u8,)", "(u8, &'static str)", "!", "_", "[u8; 0x20]", "[u8; { N }]",
+			"&mut [u8]", "*const (dyn Any + Send)", "&dyn Any", "Box<dyn Any + Send + 'static>",
+			"std::vec::Vec<u8>", "Vec::<u8>", "Pin<&mut (dyn Future<Output = ()> + Send)>",
+			"fn(u8, u16) -> bool", "unsafe extern \"C\" fn(*const u8) -> !", "Box<dyn Fn(u8) -> u8 + Send>",
+			"C<'>'>", r"C<'\''>", r"C<'\u{3e}', '\\'>", "C<-4, true, 'a>",
+		] {
+			assert_parses(name);
+		}
+	}
+
+	#[test]
+	fn rejects_non_types() {
+		for name in [
+			"", "&dyn Any + Send", "<unknown>", "Vec<…>", "Vec<u8", "Vec<u8>>", "fn(u8) ->",
+			"*[u8]", "(u8", "Vec(u8)", "[u8; ]", "&(dyn Any + Send", "dyn", r"C<'\'>",
+		] {
+			assert!(parse(name).is_err(), "`{}` parsed as Rust type", name);
+		}
+	}
+}
+
+mod golden {
+	use super::assert_type_name;
+	use std::{
+		borrow::Cow,
+		collections::{HashMap, HashSet, LinkedList, VecDeque},
+		num::NonZeroU32,
+		ops::Range,
+		rc::Rc,
+		sync::{Arc, Mutex},
+	};
+
+	#[test]
+	fn golden() {
+		assert_type_name::<bool>("bool");
+		assert_type_name::<char>("char");
+		assert_type_name::<u8>("u8");
+		assert_type_name::<i128>("i128");
+		assert_type_name::<f64>("f64");
+		assert_type_name::<str>("str");
+		assert_type_name::<String>("String");
+		assert_type_name::<()>("()");
+		assert_type_name::<(u8,)>("(u8,)");
+		assert_type_name::<(u8, String, bool)>("(u8, String, bool)");
+		assert_type_name::<[u8; 32]>("[u8; 32]");
+		assert_type_name::<[u16]>("[u16]");
+		assert_type_name::<&str>("&str");
+		assert_type_name::<&mut [u8]>("&mut [u8]");
+		assert_type_name::<*const u8>("*const u8");
+		assert_type_name::<*mut [u8; 4]>("*mut [u8; 4]");
+		assert_type_name::<fn() -> ()>("fn()");
+		assert_type_name::<fn(u8, u16) -> bool>("fn(u8, u16) -> bool");
+		assert_type_name::<Box<str>>("Box<str>");
+		assert_type_name::<Rc<[u8]>>("Rc<[u8]>");
+		assert_type_name::<Arc<Mutex<u32>>>("Arc<Mutex<u32>>");
+		assert_type_name::<Option<u32>>("Option<u32>");
+		assert_type_name::<Result<u32, String>>("Result<u32, String>");
+		assert_type_name::<Vec<Vec<u8>>>("Vec<Vec<u8>>");
+		assert_type_name::<VecDeque<char>>("VecDeque<char>");
+		assert_type_name::<LinkedList<i8>>("LinkedList<i8>");
+		assert_type_name::<HashMap<String, Vec<u32>>>("HashMap<String, Vec<u32>>");
+		assert_type_name::<HashSet<u64>>("HashSet<u64>");
+		assert_type_name::<Cow<str>>("Cow<str>");
+		assert_type_name::<NonZeroU32>("NonZero<u32>");
+		assert_type_name::<Range<usize>>("Range<usize>");
+		assert_type_name::<Option<&(u8, [i32; 2])>>("Option<&(u8, [i32; 2])>");
+	}
+
+	/// Asserts that the tokenizable name of `T` is its usual name
+	/// and parses as a Rust type.
+	fn assert_tokenizable<T>()
+	where
+		T: crate::TypeName + ?Sized
+	{
+		let name = crate::tokenizable_type_name::<T>();
+		super::type_grammar::assert_parses(&name);
+		assert_eq!(name, crate::type_name::<T>());
+	}
+
+	#[test]
+	fn tokenizable() {
+		assert_tokenizable::<bool>();
+		assert_tokenizable::<str>();
+		assert_tokenizable::<String>();
+		assert_tokenizable::<()>();
+		assert_tokenizable::<(u8,)>();
+		assert_tokenizable::<(u8, String, bool)>();
+		assert_tokenizable::<[u8; 32]>();
+		assert_tokenizable::<&mut [u8]>();
+		assert_tokenizable::<*mut [u8; 4]>();
+		assert_tokenizable::<fn() -> ()>();
+		assert_tokenizable::<fn(u8, u16) -> bool>();
+		assert_tokenizable::<Rc<[u8]>>();
+		assert_tokenizable::<Arc<Mutex<u32>>>();
+		assert_tokenizable::<Result<u32, String>>();
+		assert_tokenizable::<VecDeque<char>>();
+		assert_tokenizable::<LinkedList<i8>>();
+		assert_tokenizable::<HashMap<String, Vec<u32>>>();
+		assert_tokenizable::<HashSet<u64>>();
+		assert_tokenizable::<Cow<str>>();
+		assert_tokenizable::<NonZeroU32>();
+		assert_tokenizable::<Range<usize>>();
+		assert_tokenizable::<Option<&(u8, [i32; 2])>>();
+	}
+}
+
+#[test]
+fn tokenizable_type_name() {
+	use crate::{tokenizable_type_name, Unknown};
+	use std::{any::Any, future::Future, pin::Pin};
+
+	assert_eq!(tokenizable_type_name::<&(dyn Any + Send)>(), "&(dyn Any + Send)");
+	assert_eq!(tokenizable_type_name::<*const (dyn Any + Send + Sync)>(), "*const (dyn Any + Send + Sync)");
+	assert_eq!(tokenizable_type_name::<&dyn Any>(), "&dyn Any");
+	assert_eq!(tokenizable_type_name::<Box<dyn Any + Send>>(), "Box<dyn Any + Send>");
+	assert_eq!(
+		tokenizable_type_name::<Pin<&mut (dyn Future<Output = ()> + Send)>>(),
+		"Pin<&mut (dyn Future<Output = ()> + Send)>"
+	);
+	assert_eq!(tokenizable_type_name::<Vec<Unknown<str>>>(), "Vec<_>");
+	for name in [
+		tokenizable_type_name::<&(dyn Any + Send)>(),
+		tokenizable_type_name::<*const (dyn Any + Send + Sync)>(),
+		tokenizable_type_name::<Pin<&mut (dyn Future<Output = ()> + Send)>>(),
+		tokenizable_type_name::<Pin<Box<dyn Future<Output = u8> + Send>>>(),
+		tokenizable_type_name::<Vec<Unknown<str>>>(),
+		tokenizable_type_name::<(Unknown<u8>, [Unknown<u8>; 2])>(),
+		tokenizable_type_name::<fn(&'static dyn Any) -> Vec<u8>>(),
+		tokenizable_type_name::<unsafe extern "C" fn(*const u8, Option<&'static mut [u8]>) -> bool>(),
+		tokenizable_type_name::<fn(fn(u8) -> u8) -> fn()>(),
+	] {
+		type_grammar::assert_parses(&name);
+	}
+	assert!(type_grammar::parse(&type_name::<&(dyn Any + Send)>()).is_err());
+	assert!(type_grammar::parse(&type_name::<Vec<Unknown<str>>>()).is_err());
+	assert!(crate::__has_additional_bounds("dyn Fn(u8) -> u8 + Send"));
+	assert!(!crate::__has_additional_bounds("dyn Fn(u8) -> u8"));
+	assert!(!crate::__has_additional_bounds("dyn Trait<A = Box<dyn Any + Send>>"));
+}
+
+#[test]
+fn qualified() {
+	use crate::qualified_type_name;
+	use std::{collections::HashMap, rc};
+
+	assert_eq!(qualified_type_name::<Box<Vec<i32>>>(), "std::boxed::Box<std::vec::Vec<i32>>");
+	assert_eq!(qualified_type_name::<i32>(), "i32");
+	assert_eq!(qualified_type_name::<(bool, &str)>(), "(bool, &str)");
+	assert_eq!(
+		qualified_type_name::<HashMap<String, Option<u8>>>(),
+		"std::collections::HashMap<std::string::String, std::option::Option<u8>>"
+	);
+	assert_eq!(
+		qualified_type_name::<Result<rc::Weak<u8>, core::sync::atomic::Ordering>>(),
+		"std::result::Result<std::rc::Weak<u8>, std::sync::atomic::Ordering>"
+	);
+	// The short form is not affected by the qualified mode.
+	assert_type_name::<Box<Vec<i32>>>("Box<Vec<i32>>");
+}
+
+#[test]
+fn phantom_data() {
+	use crate::qualified_type_name;
+	use core::marker::PhantomData;
+
+	assert_type_name::<PhantomData<i32>>("PhantomData<i32>");
+	assert_type_name::<PhantomData<Box<str>>>("PhantomData<Box<str>>");
+	assert_type_name::<PhantomData<PhantomData<[u8]>>>("PhantomData<PhantomData<[u8]>>");
+	assert_eq!(
+		qualified_type_name::<PhantomData<Box<str>>>(),
+		"std::marker::PhantomData<std::boxed::Box<str>>"
+	);
+}
+
+#[test]
+fn cells() {
+	use crate::FormatConfig;
+	use core::cell::{Cell, RefCell, UnsafeCell};
+	use std::rc::Rc;
+
+	assert_type_name::<Cell<i32>>("Cell<i32>");
+	assert_type_name::<RefCell<i32>>("RefCell<i32>");
+	assert_type_name::<UnsafeCell<i32>>("UnsafeCell<i32>");
+	assert_type_name::<RefCell<Vec<u8>>>("RefCell<Vec<u8>>");
+	assert_type_name::<Rc<RefCell<[u8]>>>("Rc<RefCell<[u8]>>");
+	assert_type_name::<UnsafeCell<Cell<Option<bool>>>>("UnsafeCell<Cell<Option<bool>>>");
+	let config = FormatConfig {
+		c_pointer_smartptrs: true,
+		..FormatConfig::default()
+	};
+	assert_eq!(crate::type_name_with::<Rc<RefCell<u8>>>(&config), "RefCell<u8>*");
+}
+
+#[test]
+fn sorted_index_composition() {
+	use crate::qualified_type_name;
+	use std::{
+		collections::{BTreeMap, BTreeSet},
+		sync::{Arc, Mutex},
+	};
+
+	type Index = Arc<Mutex<BTreeMap<String, BTreeSet<u32>>>>;
+
+	assert_type_name::<Index>("Arc<Mutex<BTreeMap<String, BTreeSet<u32>>>>");
+	assert_type_name::<Vec<Index>>("Vec<Arc<Mutex<BTreeMap<String, BTreeSet<u32>>>>>");
+	assert_eq!(
+		qualified_type_name::<Index>(),
+		"std::sync::Arc<std::sync::Mutex<std::collections::BTreeMap<\
+		std::string::String, std::collections::BTreeSet<u32>>>>"
+	);
+}
+
+#[test]
+fn locks() {
+	use std::sync::{Arc, Mutex, RwLock};
+
+	assert_type_name::<Mutex<Vec<u8>>>("Mutex<Vec<u8>>");
+	assert_type_name::<RwLock<String>>("RwLock<String>");
+	assert_type_name::<Arc<RwLock<[u8]>>>("Arc<RwLock<[u8]>>");
+	assert_type_name::<RwLock<Mutex<Option<bool>>>>("RwLock<Mutex<Option<bool>>>");
+}
+
+#[test]
+fn non_null() {
+	use core::ptr::NonNull;
+
+	assert_type_name::<NonNull<u8>>("NonNull<u8>");
+	assert_type_name::<NonNull<[u8]>>("NonNull<[u8]>");
+	assert_type_name::<Option<NonNull<dyn std::any::Any>>>("Option<NonNull<dyn Any>>");
+	assert_eq!(module_path_of::<NonNull<u8>>(), Some("std::ptr"));
+}
+
+#[test]
+fn atomics() {
+	use core::sync::atomic::*;
+
+	assert_type_name::<AtomicBool>("AtomicBool");
+	assert_type_name::<AtomicU8>("AtomicU8");
+	assert_type_name::<AtomicI8>("AtomicI8");
+	assert_type_name::<AtomicU16>("AtomicU16");
+	assert_type_name::<AtomicI16>("AtomicI16");
+	assert_type_name::<AtomicU32>("AtomicU32");
+	assert_type_name::<AtomicI32>("AtomicI32");
+	assert_type_name::<AtomicUsize>("AtomicUsize");
+	assert_type_name::<AtomicIsize>("AtomicIsize");
+	assert_type_name::<AtomicPtr<u8>>("AtomicPtr<u8>");
+	assert_type_name::<std::sync::Arc<AtomicBool>>("Arc<AtomicBool>");
+	assert_eq!(module_path_of::<AtomicU32>(), Some("std::sync::atomic"));
+}
+
+#[cfg(target_has_atomic = "ptr")]
+#[test]
+fn atomic_ptr() {
+	use core::sync::atomic::AtomicPtr;
+
+	assert_type_name::<AtomicPtr<u8>>("AtomicPtr<u8>");
+	assert_type_name::<AtomicPtr<Vec<String>>>("AtomicPtr<Vec<String>>");
+	assert_type_name::<AtomicPtr<AtomicPtr<()>>>("AtomicPtr<AtomicPtr<()>>");
+	assert_eq!(module_path_of::<AtomicPtr<u8>>(), Some("std::sync::atomic"));
+}
+
+#[cfg(target_has_atomic = "64")]
+#[test]
+fn atomics_64() {
+	use core::sync::atomic::{AtomicI64, AtomicU64};
+
+	assert_type_name::<AtomicU64>("AtomicU64");
+	assert_type_name::<AtomicI64>("AtomicI64");
+}
+
+#[test]
+fn paths_and_os_strings() {
+	use std::{
+		ffi::{OsStr, OsString},
+		path::{Path, PathBuf},
+		rc::Rc,
+	};
+
+	assert_type_name::<PathBuf>("PathBuf");
+	assert_type_name::<Path>("Path");
+	assert_type_name::<OsString>("OsString");
+	assert_type_name::<OsStr>("OsStr");
+	assert_type_name::<Box<Path>>("Box<Path>");
+	assert_type_name::<&OsStr>("&OsStr");
+	assert_type_name::<Rc<OsStr>>("Rc<OsStr>");
+	assert_type_name::<Vec<PathBuf>>("Vec<PathBuf>");
+	assert_eq!(module_path_of::<Path>(), Some("std::path"));
+	assert_eq!(module_path_of::<OsString>(), Some("std::ffi"));
+}
+
+#[test]
+fn c_strings() {
+	use std::ffi::{CStr, CString};
+
+	assert_type_name::<CString>("CString");
+	assert_type_name::<CStr>("CStr");
+	assert_type_name::<&CStr>("&CStr");
+	assert_type_name::<Box<CStr>>("Box<CStr>");
+	assert_type_name::<Result<CString, String>>("Result<CString, String>");
+	assert_eq!(module_path_of::<CStr>(), Some("std::ffi"));
+}
+
+#[test]
+fn net_addrs() {
+	use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+	assert_type_name::<IpAddr>("IpAddr");
+	assert_type_name::<Ipv4Addr>("Ipv4Addr");
+	assert_type_name::<Ipv6Addr>("Ipv6Addr");
+	assert_type_name::<SocketAddr>("SocketAddr");
+	assert_type_name::<SocketAddrV4>("SocketAddrV4");
+	assert_type_name::<SocketAddrV6>("SocketAddrV6");
+	assert_type_name::<Vec<SocketAddr>>("Vec<SocketAddr>");
+	assert_eq!(module_path_of::<IpAddr>(), Some("std::net"));
+}
+
+#[test]
+fn manually_drop_and_maybe_uninit() {
+	use core::mem::{ManuallyDrop, MaybeUninit};
+
+	assert_type_name::<ManuallyDrop<String>>("ManuallyDrop<String>");
+	assert_type_name::<ManuallyDrop<str>>("ManuallyDrop<str>");
+	assert_type_name::<MaybeUninit<[u8; 16]>>("MaybeUninit<[u8; 16]>");
+	assert_type_name::<[MaybeUninit<u32>; 4]>("[MaybeUninit<u32>; 4]");
+	assert_type_name::<MaybeUninit<ManuallyDrop<Vec<u8>>>>("MaybeUninit<ManuallyDrop<Vec<u8>>>");
+	assert_eq!(module_path_of::<MaybeUninit<u8>>(), Some("std::mem"));
+}
+
+#[test]
+fn reverse() {
+	use core::{
+		cmp::Reverse,
+		num::{Saturating, Wrapping},
+	};
+	use std::collections::BinaryHeap;
+
+	assert_type_name::<Reverse<i64>>("Reverse<i64>");
+	assert_type_name::<Vec<Reverse<u32>>>("Vec<Reverse<u32>>");
+	assert_type_name::<BinaryHeap<Reverse<(u8, String)>>>("BinaryHeap<Reverse<(u8, String)>>");
+	assert_type_name::<Reverse<Wrapping<i32>>>("Reverse<Wrapping<i32>>");
+	assert_type_name::<Saturating<u8>>("Saturating<u8>");
+	assert_eq!(module_path_of::<Reverse<i64>>(), Some("std::cmp"));
+}
+
+#[test]
+fn write_io() {
+	use crate::write_type_name_io;
+	use std::io;
+
+	fn assert_io<T>()
+	where
+		T: TypeName + ?Sized
+	{
+		let mut bytes = Vec::new();
+		write_type_name_io::<T, _>(&mut bytes).unwrap();
+		assert_eq!(bytes, type_name::<T>().as_bytes());
+	}
+
+	assert_io::<u32>();
+	assert_io::<Vec<Option<String>>>();
+	assert_io::<&mut [(u8, bool)]>();
+
+	// The error of the underlying writer is propagated.
+	let mut buffer = [0_u8; 4];
+	let error = write_type_name_io::<Vec<u32>, _>(&mut &mut buffer[..]).unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+}
+
+#[test]
+fn display_type_name() {
+	use crate::{display_type_name, TypeNameDisplay};
+
+	assert_eq!(format!("{}", display_type_name::<Box<str>>()), "Box<str>");
+	assert_eq!(format!("{}", display_type_name::<str>()), "str");
+	let display: TypeNameDisplay<Vec<i32>> = display_type_name();
+	assert_eq!(display.to_string(), "Vec<i32>");
+}
+
+#[test]
+fn fmt_type_name() {
+	use core::{fmt, marker::PhantomData};
+
+	/// A typed handle whose `Debug` impl names the handled type.
+	struct Handle<T>(usize, PhantomData<T>);
+
+	impl<T> fmt::Debug for Handle<T>
+	where
+		T: TypeName
+	{
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			f.write_str("Handle<")?;
+			crate::fmt_type_name::<T>(f)?;
+			write!(f, ">#{}", self.0)
+		}
+	}
+
+	assert_eq!(format!("{:?}", Handle::<u8>(1, PhantomData)), "Handle<u8>#1");
+	assert_eq!(
+		format!("{:?}", Handle::<Vec<Option<bool>>>(2, PhantomData)),
+		"Handle<Vec<Option<bool>>>#2"
+	);
+}
+
+#[test]
+fn into() {
+	use crate::type_name_into;
+
+	let mut buffer = String::new();
+	type_name_into::<Option<u8>>(&mut buffer);
+	type_name_into::<&str>(&mut buffer);
+	assert_eq!(buffer, "Option<u8>&str");
+
+	let mut list = String::from("types: ");
+	for (n, push) in [type_name_into::<bool>, type_name_into::<Box<[i32]>>].iter().enumerate() {
+		if n != 0 {
+			list.push_str(", ");
+		}
+		push(&mut list);
+	}
+	assert_eq!(list, "types: bool, Box<[i32]>");
+}
+
+#[test]
+fn static_type_name() {
+	use crate::static_type_name;
+
+	assert_eq!(static_type_name::<u32>(), Some("u32"));
+	assert_eq!(static_type_name::<str>(), Some("str"));
+	assert_eq!(static_type_name::<String>(), Some("String"));
+	assert_eq!(static_type_name::<()>(), Some("()"));
+	assert_eq!(static_type_name::<dyn std::any::Any>(), Some("dyn Any"));
+	assert_eq!(static_type_name::<Handle>(), Some("ForeignHandle"));
+	assert_eq!(static_type_name::<Vec<u32>>(), None);
+	assert_eq!(static_type_name::<&u32>(), None);
+	assert_eq!(static_type_name::<(u8,)>(), None);
+}
+
+#[test]
+fn type_name_cow() {
+	use crate::type_name_cow;
+	use std::borrow::Cow;
+
+	assert!(matches!(type_name_cow::<bool>(), Cow::Borrowed("bool")));
+	assert!(matches!(type_name_cow::<String>(), Cow::Borrowed("String")));
+	assert!(matches!(type_name_cow::<Option<bool>>(), Cow::Owned(ref name) if name == "Option<bool>"));
+	assert!(matches!(type_name_cow::<[u8]>(), Cow::Owned(ref name) if name == "[u8]"));
+}
+
+#[test]
+fn len_hint() {
+	type Nested = Box<Vec<Result<i32, String>>>;
+
+	assert_eq!(u32::type_name_len_hint(), 3);
+	assert_eq!(Nested::type_name_len_hint(), "Box<Vec<Result<i32, String>>>".len());
+	let name = type_name::<Nested>();
+	assert_eq!(name, "Box<Vec<Result<i32, String>>>");
+	// The buffer is allocated once with the exact size and never grows.
+	assert_eq!(name.capacity(), name.len());
+}
+
+#[test]
+fn long_tuples() {
+	assert_type_name::<(i32, i32, i32, i32, i32, i32, i32, i32, i32, i32, i32)>(
+		"(i32, i32, i32, i32, i32, i32, i32, i32, i32, i32, i32)"
+	);
+	assert_type_name::<(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, (bool, char), ())>(
+		"(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, (bool, char), ())"
+	);
+	assert_eq!(<(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8)>::GENERIC_ARITY, 12);
+}
+
+#[test]
+fn std_compat() {
+	use crate::std_compat_type_name;
+	use std::{
+		any,
+		collections::{BTreeMap, HashMap, VecDeque},
+		rc::Rc,
+		sync::Arc,
+	};
+
+	fn assert_std_compat<T>()
+	where
+		T: TypeName + ?Sized
+	{
+		assert_eq!(std_compat_type_name::<T>(), any::type_name::<T>());
+	}
+
+	assert_std_compat::<Vec<i32>>();
+	assert_std_compat::<Box<str>>();
+	assert_std_compat::<Option<u8>>();
+	assert_std_compat::<(i32, &str, [u8; 4])>();
+	assert_std_compat::<Result<String, Rc<u8>>>();
+	assert_std_compat::<Arc<[u16]>>();
+	assert_std_compat::<HashMap<u8, BTreeMap<u8, VecDeque<u8>>>>();
+	assert_std_compat::<core::cell::RefCell<core::num::NonZero<u32>>>();
+	assert_std_compat::<core::time::Duration>();
+
+	// Known divergences.
+	assert_eq!(std_compat_type_name::<std::borrow::Cow<str>>(), "alloc::borrow::Cow<str>");
+	assert_eq!(std_compat_type_name::<&dyn any::Any>(), "&dyn Any");
+}
+
+#[test]
+fn space_after_comma() {
+	use std::collections::HashMap;
+
+	let compact = FormatConfig {
+		space_after_comma: false,
+		..FormatConfig::default()
+	};
+	assert!(FormatConfig::default().space_after_comma);
+	assert_eq!(type_name_with::<(i32, u32)>(&compact), "(i32,u32)");
+	assert_eq!(type_name_with::<Result<i32, String>>(&compact), "Result<i32,String>");
+	assert_eq!(type_name_with::<fn(i32, bool)>(&compact), "fn(i32,bool)");
+	assert_eq!(type_name_with::<HashMap<u8, (u8, u8)>>(&compact), "HashMap<u8,(u8,u8)>");
+	assert_eq!(type_name_with::<(u8,)>(&compact), "(u8,)");
+	// Function parameters are separated like tuple elements by default.
+	assert_type_name::<fn(i32, bool)>("fn(i32, bool)");
+	assert_type_name::<(i32, bool)>("(i32, bool)");
+	assert_type_name::<Result<i32, String>>("Result<i32, String>");
+}
+
+#[test]
+fn array_len_format() {
+	let hex = FormatConfig {
+		hex_array_lens: true,
+		..FormatConfig::default()
+	};
+	let grouped = FormatConfig {
+		group_array_len_digits: true,
+		..FormatConfig::default()
+	};
+	let grouped_hex = FormatConfig {
+		hex_array_lens: true,
+		group_array_len_digits: true,
+		..FormatConfig::default()
+	};
+	assert_type_name::<[u8; 1024]>("[u8; 1024]");
+	assert_eq!(type_name_with::<[u8; 1024]>(&hex), "[u8; 0x400]");
+	assert_eq!(type_name_with::<[u8; 1024]>(&grouped), "[u8; 1_024]");
+	assert_eq!(type_name_with::<[u8; 1024]>(&grouped_hex), "[u8; 0x400]");
+	assert_eq!(type_name_with::<[u8; 0]>(&grouped), "[u8; 0]");
+	assert_eq!(type_name_with::<[u8; 0]>(&hex), "[u8; 0x0]");
+	assert_eq!(type_name_with::<[u8; 999]>(&grouped), "[u8; 999]");
+	assert_eq!(type_name_with::<[u8; 1_048_576]>(&grouped), "[u8; 1_048_576]");
+	assert_eq!(type_name_with::<[u8; 0x10000]>(&grouped_hex), "[u8; 0x1_0000]");
+	assert_eq!(type_name_with::<[[u8; 16]; 4096]>(&hex), "[[u8; 0x10]; 0x1000]");
+}
+
+#[test]
+fn slices_in_smart_pointers() {
+	use std::{rc::Rc, sync::Arc};
+
+	assert_type_name::<Box<[u8]>>("Box<[u8]>");
+	assert_type_name::<Rc<[u8]>>("Rc<[u8]>");
+	assert_type_name::<Arc<[u8]>>("Arc<[u8]>");
+	assert_type_name::<Arc<[String]>>("Arc<[String]>");
+	assert_type_name::<Rc<[(u8, bool)]>>("Rc<[(u8, bool)]>");
+	assert_type_name::<Box<[Arc<[u8]>]>>("Box<[Arc<[u8]>]>");
+}
+
+#[test]
+fn fn_with_slice_and_array_refs() {
+	// Elided lifetimes in parameters make function pointers higher-ranked
+	// which are not supported, see `impl_fn_signature_hash!`.
+	assert_type_name::<fn(&'static [u8]) -> usize>("fn(&[u8]) -> usize");
+	assert_type_name::<fn(&'static [u8; 32]) -> bool>("fn(&[u8; 32]) -> bool");
+	assert_type_name::<fn(&'static mut [[u8; 3]], &'static [u8; 57])>("fn(&mut [[u8; 3]], &[u8; 57])");
+	assert_type_name::<fn(&'static [&'static [u8]]) -> [u8; 0]>("fn(&[&[u8]]) -> [u8; 0]");
+}
+
+#[test]
+fn c_void() {
+	use core::ffi::{c_char, c_int, c_uint, c_void};
+
+	assert_type_name::<c_void>("c_void");
+	assert_type_name::<*const c_void>("*const c_void");
+	assert_type_name::<*mut c_void>("*mut c_void");
+	assert_type_name::<extern "C" fn(*mut c_void) -> u8>("extern \"C\" fn(*mut c_void) -> u8");
+	// The C type aliases are written as the primitives they alias.
+	assert_eq!(type_name::<c_int>(), type_name::<i32>());
+	assert_eq!(type_name::<c_uint>(), type_name::<u32>());
+	assert!(type_name::<c_char>() == "i8" || type_name::<c_char>() == "u8");
+}
+
+#[test]
+fn phantom_pinned() {
+	use core::marker::PhantomPinned;
+
+	assert_type_name::<PhantomPinned>("PhantomPinned");
+	assert_type_name::<(PhantomPinned, i32)>("(PhantomPinned, i32)");
+	assert_eq!(module_path_of::<PhantomPinned>(), Some("std::marker"));
+}
+
+#[test]
+fn infallible() {
+	use core::convert::Infallible;
+
+	assert_type_name::<Infallible>("Infallible");
+	assert_type_name::<Result<u32, Infallible>>("Result<u32, Infallible>");
+	assert_eq!(module_path_of::<Infallible>(), Some("std::convert"));
+	assert_eq!(
+		crate::std_compat_type_name::<Result<u32, Infallible>>(),
+		std::any::type_name::<Result<u32, Infallible>>()
+	);
+}
+
+#[test]
+fn type_name_truncated() {
+	use crate::type_name_truncated;
+	use std::collections::HashMap;
+
+	type Nested = Vec<HashMap<String, Vec<Result<Box<[u8]>, String>>>>;
+
+	assert_eq!(type_name_truncated::<Nested>(0), "…");
+	assert_eq!(type_name_truncated::<Nested>(1), "Vec<…>");
+	assert_eq!(type_name_truncated::<Nested>(2), "Vec<HashMap<…>>");
+	assert_eq!(type_name_truncated::<Nested>(4), "Vec<HashMap<String, Vec<Result<…>>>>");
+	assert_eq!(type_name_truncated::<Nested>(5), "Vec<HashMap<String, Vec<Result<Box<…>, String>>>>");
+	assert_eq!(type_name_truncated::<Nested>(6), type_name::<Nested>());
+	assert_eq!(type_name_truncated::<u32>(1), "u32");
+	assert_eq!(type_name_truncated::<(Vec<u8>, u8)>(1), "(Vec<…>, u8)");
+	assert_eq!(type_name_truncated::<fn() -> Vec<Vec<u8>>>(1), "fn() -> Vec<…>");
+}
+
+#[test]
+fn with_type_name() {
+	use crate::with_type_name;
+
+	assert_eq!(with_type_name::<u32, _>(str::to_owned), "u32");
+	assert_eq!(with_type_name::<Vec<Option<u8>>, _>(str::to_owned), "Vec<Option<u8>>");
+	// The buffer is cleared between calls.
+	assert_eq!(with_type_name::<u8, _>(str::to_owned), "u8");
+	// Nested calls do not observe the outer name being overwritten.
+	let names = with_type_name::<Vec<u8>, _>(|outer| {
+		let inner = with_type_name::<bool, _>(str::to_owned);
+		(String::from(outer), inner)
+	});
+	assert_eq!(names, (String::from("Vec<u8>"), String::from("bool")));
+}
+
+#[test]
+fn once_cells() {
+	use core::cell::OnceCell;
+	use std::{
+		collections::HashMap,
+		sync::{Arc, OnceLock},
+	};
+
+	assert_type_name::<OnceCell<u8>>("OnceCell<u8>");
+	assert_type_name::<OnceLock<String>>("OnceLock<String>");
+	assert_type_name::<OnceLock<HashMap<String, u32>>>("OnceLock<HashMap<String, u32>>");
+	assert_type_name::<Arc<OnceLock<Vec<u8>>>>("Arc<OnceLock<Vec<u8>>>");
+	assert_eq!(module_path_of::<OnceCell<u8>>(), Some("std::cell"));
+	assert_eq!(module_path_of::<OnceLock<u8>>(), Some("std::sync"));
+}
+
+/// A visitor that reconstructs the flat type name.
+#[derive(Default)]
+struct FlatVisitor(String);
+
+impl crate::TypeNameVisitor for FlatVisitor {
+	fn enter_generic(&mut self, name: &str) {
+		self.0.push_str(name);
+		self.0.push('<');
+	}
+
+	fn exit_generic(&mut self) {
+		self.0.push('>');
+	}
+
+	fn separator(&mut self) {
+		self.0.push_str(", ");
+	}
+
+	fn leaf(&mut self, name: &str) {
+		self.0.push_str(name);
+	}
+
+	fn punct(&mut self, punct: &str) {
+		self.0.push_str(punct);
+	}
+}
+
+#[test]
+fn visit_type_name() {
+	use crate::visit_type_name;
+	use std::{borrow::Cow, collections::HashMap, future::Future, pin::Pin};
+
+	fn assert_flat<T>()
+	where
+		T: TypeName + ?Sized
+	{
+		let mut visitor = FlatVisitor::default();
+		visit_type_name::<T, _>(&mut visitor);
+		assert_eq!(visitor.0, type_name::<T>());
+	}
+
+	assert_flat::<u8>();
+	assert_flat::<()>();
+	assert_flat::<(u8,)>();
+	assert_flat::<(u8, (bool,), [i32; 4])>();
+	assert_flat::<Vec<HashMap<String, Option<u8>>>>();
+	assert_flat::<&'static mut [Cow<'static, str>]>();
+	assert_flat::<*const fn(u8, bool) -> Vec<u8>>();
+	assert_flat::<unsafe extern "C" fn(u8)>();
+	assert_flat::<Pin<Box<dyn Future<Output = u8> + Send>>>();
+	assert_flat::<std::collections::btree_map::Range<u8, u8>>();
+}
+
+#[test]
+fn visit_type_name_structure() {
+	use crate::{visit_type_name, TypeNameVisitor};
+
+	#[derive(Default)]
+	struct Events(Vec<String>);
+
+	impl TypeNameVisitor for Events {
+		fn enter_generic(&mut self, name: &str) {
+			self.0.push(format!("enter {}", name));
+		}
+
+		fn exit_generic(&mut self) {
+			self.0.push(String::from("exit"));
+		}
+
+		fn separator(&mut self) {
+			self.0.push(String::from("separator"));
+		}
+
+		fn leaf(&mut self, name: &str) {
+			self.0.push(format!("leaf {}", name));
+		}
+	}
+
+	let mut events = Events::default();
+	visit_type_name::<Result<Vec<u8>, String>, _>(&mut events);
+	assert_eq!(events.0, [
+		"enter Result", "enter Vec", "leaf u8", "exit", "separator", "leaf String", "exit",
+	]);
+}
+
+#[test]
+fn to_owned_projections() {
+	fn owned_name<B>() -> String
+	where
+		B: ToOwned + ?Sized,
+		B::Owned: TypeName,
+	{
+		type_name::<B::Owned>()
+	}
+
+	assert_type_name::<<str as ToOwned>::Owned>("String");
+	assert_type_name::<<[u8] as ToOwned>::Owned>("Vec<u8>");
+	assert_type_name::<<u32 as ToOwned>::Owned>("u32");
+	assert_eq!(owned_name::<str>(), "String");
+	assert_eq!(owned_name::<[Option<u8>]>(), "Vec<Option<u8>>");
+	assert_eq!(owned_name::<std::path::Path>(), "PathBuf");
+	assert_eq!(owned_name::<std::ffi::CStr>(), "CString");
+}
+
+#[test]
+fn mixed_tuples() {
+	assert_type_name::<(&'static [u8], usize, &'static mut Vec<u8>)>("(&[u8], usize, &mut Vec<u8>)");
+	assert_type_name::<(fn(u8) -> u8, [i32; 4])>("(fn(u8) -> u8, [i32; 4])");
+	assert_type_name::<(&'static str, ())>("(&str, ())");
+	assert_type_name::<(u8, fn(), ())>("(u8, fn(), ())");
+	assert_type_name::<(*const [u8], &'static (u8, ()))>("(*const [u8], &(u8, ()))");
+}
+
+#[test]
+fn naive_batches() {
+	use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+	use std::time::{Duration, Instant, SystemTime};
+
+	assert_type_name::<IpAddr>("IpAddr");
+	assert_type_name::<Ipv4Addr>("Ipv4Addr");
+	assert_type_name::<Ipv6Addr>("Ipv6Addr");
+	assert_type_name::<SocketAddr>("SocketAddr");
+	assert_type_name::<SocketAddrV4>("SocketAddrV4");
+	assert_type_name::<SocketAddrV6>("SocketAddrV6");
+	assert_type_name::<Duration>("Duration");
+	assert_type_name::<Instant>("Instant");
+	assert_type_name::<SystemTime>("SystemTime");
+	assert_eq!(module_path_of::<SocketAddrV6>(), Some("std::net"));
+	assert_eq!(module_path_of::<SystemTime>(), Some("std::time"));
+	assert_eq!(crate::qualified_type_name::<Instant>(), "std::time::Instant");
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn custom_allocator() {
+	use std::{
+		alloc::System,
+		collections::{BinaryHeap, LinkedList, VecDeque},
+		rc::Rc,
+		sync::Arc,
+	};
+
+	assert_type_name::<Box<u8, System>>("Box<u8>");
+	assert_type_name::<Box<[u8], System>>("Box<[u8]>");
+	assert_type_name::<Rc<str, System>>("Rc<str>");
+	assert_type_name::<Arc<u8, System>>("Arc<u8>");
+	assert_type_name::<Vec<Vec<u8, System>, System>>("Vec<Vec<u8>>");
+	assert_type_name::<VecDeque<u8, System>>("VecDeque<u8>");
+	assert_type_name::<LinkedList<u8, System>>("LinkedList<u8>");
+	assert_type_name::<BinaryHeap<u8, System>>("BinaryHeap<u8>");
+}
+
+#[test]
+fn type_name_pretty() {
+	use crate::type_name_pretty;
+	use std::collections::HashMap;
+
+	assert_eq!(type_name_pretty::<u8>(), "u8");
+	assert_eq!(type_name_pretty::<(u8, [bool; 2])>(), "(u8, [bool; 2])");
+	assert_eq!(
+		type_name_pretty::<Result<Vec<u8>, String>>(),
+		"\
+Result<
+    Vec<
+        u8
+    >,
+    String
+>"
+	);
+	assert_eq!(
+		type_name_pretty::<HashMap<(u8, Vec<u8>), fn(u8, bool) -> Option<u8>>>(),
+		"\
+HashMap<
+    (u8, Vec<
+        u8
+    >),
+    fn(u8, bool) -> Option<
+        u8
+    >
+>"
+	);
+}
+
+#[test]
+fn uniform_separators() {
+	use std::{collections::HashMap, future::Future};
+
+	fn params(fn_name: &str) -> &str {
+		fn_name.trim_start_matches("fn")
+	}
+
+	for config in &[
+		FormatConfig::default(),
+		FormatConfig {
+			space_after_comma: false,
+			..FormatConfig::default()
+		},
+	] {
+		let fn_name = type_name_with::<fn(u8, bool, char)>(config);
+		let tuple_name = type_name_with::<(u8, bool, char)>(config);
+		assert_eq!(params(&fn_name), tuple_name);
+		let comma = if config.space_after_comma { ", " } else { "," };
+		assert_eq!(type_name_with::<Result<u8, bool>>(config), format!("Result<u8{}bool>", comma));
+		assert_eq!(type_name_with::<HashMap<u8, bool>>(config), format!("HashMap<u8{}bool>", comma));
+		assert_eq!(
+			type_name_with::<Box<dyn Future<Output = (u8, bool)>>>(config),
+			format!("Box<dyn Future<Output = (u8{}bool)>>", comma)
+		);
+	}
+}
+
+#[test]
+fn smart_ptr_qualified() {
+	use crate::{qualified_type_name, std_compat_type_name};
+	use std::{rc::Rc, sync::Arc};
+
+	assert_eq!(qualified_type_name::<Rc<i32>>(), "std::rc::Rc<i32>");
+	assert_eq!(qualified_type_name::<Arc<i32>>(), "std::sync::Arc<i32>");
+	assert_eq!(qualified_type_name::<Box<i32>>(), "std::boxed::Box<i32>");
+	assert_eq!(std_compat_type_name::<Rc<i32>>(), "alloc::rc::Rc<i32>");
+	assert_eq!(std_compat_type_name::<Arc<i32>>(), "alloc::sync::Arc<i32>");
+	assert_eq!(std_compat_type_name::<Box<i32>>(), "alloc::boxed::Box<i32>");
+	assert_eq!(std_compat_type_name::<Arc<i32>>(), std::any::type_name::<Arc<i32>>());
+	assert_eq!(std_compat_type_name::<Rc<i32>>(), std::any::type_name::<Rc<i32>>());
+}
+
+#[test]
+fn std_errors() {
+	use crate::std_compat_type_name;
+	use std::{
+		fmt, io,
+		num::{ParseFloatError, ParseIntError},
+		str::Utf8Error,
+		string::FromUtf8Error,
+		time::SystemTimeError,
+	};
+
+	fn assert_error<E>(name: &str)
+	where
+		E: TypeName,
+	{
+		assert_type_name::<E>(name);
+		assert_type_name::<Result<u32, E>>(&format!("Result<u32, {}>", name));
+		assert_eq!(std_compat_type_name::<E>(), std::any::type_name::<E>());
+	}
+
+	assert_error::<io::Error>("io::Error");
+	assert_error::<fmt::Error>("fmt::Error");
+	assert_error::<Utf8Error>("Utf8Error");
+	assert_error::<FromUtf8Error>("FromUtf8Error");
+	assert_error::<ParseIntError>("ParseIntError");
+	assert_error::<ParseFloatError>("ParseFloatError");
+	assert_error::<SystemTimeError>("SystemTimeError");
+	assert_eq!(crate::qualified_type_name::<io::Error>(), "std::io::Error");
+}
@@ -0,0 +1,207 @@
+//! Tokenization of written type names.
+
+#[cfg(feature = "alloc")]
+use crate::TypeName;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// A token of a type name.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Token<'a> {
+	/// An identifier, keyword or number, e.g. `Vec`, `mut`, `fn` or `4`.
+	Ident(&'a str),
+	/// The `<` opening the generic arguments.
+	Open,
+	/// The `>` closing the generic arguments.
+	Close,
+	/// The `(` opening a tuple or parameter list.
+	OpenParen,
+	/// The `)` closing a tuple or parameter list.
+	CloseParen,
+	/// The `[` opening an array or slice.
+	OpenBracket,
+	/// The `]` closing an array or slice.
+	CloseBracket,
+	/// The `,` separating generic arguments, tuple elements or parameters.
+	Comma,
+	/// The `;` separating the element type and the length of an array.
+	Semicolon,
+	/// The `&` of a reference.
+	Ref,
+	/// The `*` of a raw pointer or a C-style smart pointer.
+	Ptr,
+	/// The `->` preceding the return type of a function.
+	Arrow,
+	/// The `+` separating the bounds of a trait object.
+	Plus,
+}
+
+/// Lazily splits a type name into its [`Token`]s.
+///
+/// Whitespace only separates tokens and is never yielded.
+/// Characters that are not part of any token are yielded as [`Token::Ident`].
+#[derive(Debug, Clone)]
+pub struct Tokens<'a> {
+	rest: &'a str,
+}
+
+impl<'a> Tokens<'a> {
+	/// Creates an iterator over the tokens of the given type name.
+	pub fn new(name: &'a str) -> Self {
+		Tokens { rest: name }
+	}
+}
+
+impl<'a> Iterator for Tokens<'a> {
+	type Item = Token<'a>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.rest = self.rest.trim_start();
+		let mut chars = self.rest.chars();
+		let token = match chars.next()? {
+			'<' => Token::Open,
+			'>' => Token::Close,
+			'(' => Token::OpenParen,
+			')' => Token::CloseParen,
+			'[' => Token::OpenBracket,
+			']' => Token::CloseBracket,
+			',' => Token::Comma,
+			';' => Token::Semicolon,
+			'&' => Token::Ref,
+			'*' => Token::Ptr,
+			'+' => Token::Plus,
+			'-' if chars.next() == Some('>') => {
+				self.rest = &self.rest[2..];
+				return Some(Token::Arrow)
+			}
+			c if is_ident_char(c) => {
+				let end = self.rest
+					.find(|c| !is_ident_char(c))
+					.unwrap_or(self.rest.len());
+				let (ident, rest) = self.rest.split_at(end);
+				self.rest = rest;
+				return Some(Token::Ident(ident))
+			}
+			c => {
+				let (ident, rest) = self.rest.split_at(c.len_utf8());
+				self.rest = rest;
+				return Some(Token::Ident(ident))
+			}
+		};
+		self.rest = &self.rest[1..];
+		Some(token)
+	}
+}
+
+/// Returns `true` if `c` may be part of an identifier, keyword or number.
+///
+/// Paths such as `btree_map::Range` are a single identifier.
+pub(crate) fn is_ident_char(c: char) -> bool {
+	c.is_alphanumeric() || c == '_' || c == ':' || c == '\''
+}
+
+/// An owned [`Token`] as yielded by [`IntoTokens`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OwnedToken {
+	/// An identifier, keyword or number, see [`Token::Ident`].
+	Ident(String),
+	/// Any other token which never is a [`Token::Ident`].
+	Punct(Token<'static>),
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<Token<'a>> for OwnedToken {
+	fn from(token: Token<'a>) -> Self {
+		match token {
+			Token::Ident(ident) => OwnedToken::Ident(String::from(ident)),
+			Token::Open => OwnedToken::Punct(Token::Open),
+			Token::Close => OwnedToken::Punct(Token::Close),
+			Token::OpenParen => OwnedToken::Punct(Token::OpenParen),
+			Token::CloseParen => OwnedToken::Punct(Token::CloseParen),
+			Token::OpenBracket => OwnedToken::Punct(Token::OpenBracket),
+			Token::CloseBracket => OwnedToken::Punct(Token::CloseBracket),
+			Token::Comma => OwnedToken::Punct(Token::Comma),
+			Token::Semicolon => OwnedToken::Punct(Token::Semicolon),
+			Token::Ref => OwnedToken::Punct(Token::Ref),
+			Token::Ptr => OwnedToken::Punct(Token::Ptr),
+			Token::Arrow => OwnedToken::Punct(Token::Arrow),
+			Token::Plus => OwnedToken::Punct(Token::Plus),
+		}
+	}
+}
+
+/// Lazily splits an owned type name into its [`OwnedToken`]s.
+///
+/// See [`type_name_tokens`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct IntoTokens {
+	name: String,
+	/// The byte offset of the not yet tokenized rest of the name.
+	pos: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for IntoTokens {
+	type Item = OwnedToken;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let mut tokens = Tokens::new(&self.name[self.pos..]);
+		let token = tokens.next()?;
+		self.pos = self.name.len() - tokens.rest.len();
+		Some(token.into())
+	}
+}
+
+/// Returns an iterator over the tokens of the name of the given type.
+///
+/// The name is written once and then tokenized lazily. Use
+/// [`type_name_tokens_into`] to tokenize many names without allocating
+/// an owned name and tokens for each.
+///
+/// # Example
+///
+/// ```
+/// # use tyname::{type_name_tokens, OwnedToken::*, Token::*};
+/// let tokens = type_name_tokens::<Vec<u32>>().collect::<Vec<_>>();
+/// assert_eq!(
+///     tokens,
+///     [Ident(String::from("Vec")), Punct(Open), Ident(String::from("u32")), Punct(Close)]
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn type_name_tokens<T>() -> IntoTokens
+where
+	T: TypeName + ?Sized
+{
+	let mut name = String::new();
+	T::write_type_name(&mut name)
+		.expect("[tyname::type_name_tokens] Encountered error while writing type name");
+	IntoTokens { name, pos: 0 }
+}
+
+/// Writes the name of the given type into `buffer` and returns an
+/// iterator over its tokens.
+///
+/// The previous contents of `buffer` are cleared. Reusing the buffer
+/// allows to tokenize many type names without allocating for each.
+///
+/// # Example
+///
+/// ```
+/// # use tyname::{type_name_tokens_into, Token};
+/// let mut buffer = String::new();
+/// let tokens = type_name_tokens_into::<Vec<u32>>(&mut buffer).collect::<Vec<_>>();
+/// assert_eq!(tokens, [Token::Ident("Vec"), Token::Open, Token::Ident("u32"), Token::Close]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn type_name_tokens_into<T>(buffer: &mut String) -> Tokens<'_>
+where
+	T: TypeName + ?Sized
+{
+	buffer.clear();
+	T::write_type_name(buffer)
+		.expect("[tyname::type_name_tokens_into] Encountered error while writing type name");
+	Tokens::new(buffer)
+}
@@ -0,0 +1,162 @@
+//! Programmatically built type name structures.
+
+use crate::FmtResult;
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::fmt::{self, Display, Formatter, Write};
+
+/// The structure of a type name.
+///
+/// Allows tooling to build type names programmatically and render them
+/// the same way the [`TypeName`](crate::TypeName) impls write them.
+///
+/// # Example
+///
+/// ```
+/// # use tyname::TypeNameTree;
+/// let tree = TypeNameTree::named("Vec", vec![TypeNameTree::named("u32", vec![])]);
+/// assert_eq!(tree.render(), "Vec<u32>");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TypeNameTree {
+	/// A named type and its generic arguments, e.g. `Vec<u32>` or `bool`.
+	Named {
+		/// The name of the type without its generic arguments.
+		name: String,
+		/// The generic arguments of the type.
+		args: Vec<TypeNameTree>,
+	},
+	/// A tuple type, e.g. `(u8, i8)` or `()`.
+	Tuple(Vec<TypeNameTree>),
+	/// A reference type, e.g. `&str` or `&mut bool`.
+	Reference {
+		/// Whether this is a `&mut` reference.
+		mutable: bool,
+		/// The referenced type.
+		inner: Box<TypeNameTree>,
+	},
+	/// A raw pointer type, e.g. `*const u8` or `*mut u8`.
+	Pointer {
+		/// Whether this is a `*mut` pointer.
+		mutable: bool,
+		/// The pointed-to type.
+		inner: Box<TypeNameTree>,
+	},
+	/// An array type, e.g. `[u8; 4]`.
+	Array {
+		/// The element type.
+		elem: Box<TypeNameTree>,
+		/// The length of the array.
+		len: usize,
+	},
+	/// A slice type, e.g. `[u8]`.
+	Slice(Box<TypeNameTree>),
+}
+
+impl TypeNameTree {
+	/// Creates a named type with the given generic arguments.
+	pub fn named<S>(name: S, args: Vec<TypeNameTree>) -> Self
+	where
+		S: Into<String>,
+	{
+		TypeNameTree::Named { name: name.into(), args }
+	}
+
+	/// Creates a tuple type with the given element types.
+	pub fn tuple(elems: Vec<TypeNameTree>) -> Self {
+		TypeNameTree::Tuple(elems)
+	}
+
+	/// Creates a shared or mutable reference to `inner`.
+	pub fn reference(mutable: bool, inner: TypeNameTree) -> Self {
+		TypeNameTree::Reference { mutable, inner: Box::new(inner) }
+	}
+
+	/// Creates a const or mutable raw pointer to `inner`.
+	pub fn pointer(mutable: bool, inner: TypeNameTree) -> Self {
+		TypeNameTree::Pointer { mutable, inner: Box::new(inner) }
+	}
+
+	/// Creates an array of `len` elements of type `elem`.
+	pub fn array(elem: TypeNameTree, len: usize) -> Self {
+		TypeNameTree::Array { elem: Box::new(elem), len }
+	}
+
+	/// Creates a slice of elements of type `elem`.
+	pub fn slice(elem: TypeNameTree) -> Self {
+		TypeNameTree::Slice(Box::new(elem))
+	}
+
+	/// Writes the type name represented by `self`.
+	pub fn write_to<W>(&self, w: &mut W) -> FmtResult
+	where
+		W: Write,
+	{
+		match self {
+			TypeNameTree::Named { name, args } => {
+				w.write_str(name)?;
+				if !args.is_empty() {
+					w.write_str("<")?;
+					write_separated(w, args)?;
+					w.write_str(">")?;
+				}
+				Ok(())
+			}
+			TypeNameTree::Tuple(elems) => {
+				w.write_str("(")?;
+				write_separated(w, elems)?;
+				if elems.len() == 1 {
+					// Comma needed here to differentiate between
+					// parenthesized expressions and unary-tuples
+					w.write_str(",")?;
+				}
+				w.write_str(")")
+			}
+			TypeNameTree::Reference { mutable, inner } => {
+				w.write_str(if *mutable { "&mut " } else { "&" })?;
+				inner.write_to(w)
+			}
+			TypeNameTree::Pointer { mutable, inner } => {
+				w.write_str(if *mutable { "*mut " } else { "*const " })?;
+				inner.write_to(w)
+			}
+			TypeNameTree::Array { elem, len } => {
+				w.write_str("[")?;
+				elem.write_to(w)?;
+				write!(w, "; {}]", len)
+			}
+			TypeNameTree::Slice(elem) => {
+				w.write_str("[")?;
+				elem.write_to(w)?;
+				w.write_str("]")
+			}
+		}
+	}
+
+	/// Returns the type name represented by `self`.
+	pub fn render(&self) -> String {
+		let mut buffer = String::new();
+		self.write_to(&mut buffer)
+			.expect("[tyname::TypeNameTree::render] Encountered error while writing type name");
+		buffer
+	}
+}
+
+/// Writes the given trees separated by `", "`.
+fn write_separated<W>(w: &mut W, trees: &[TypeNameTree]) -> FmtResult
+where
+	W: Write,
+{
+	for (n, tree) in trees.iter().enumerate() {
+		if n != 0 {
+			w.write_str(", ")?;
+		}
+		tree.write_to(w)?;
+	}
+	Ok(())
+}
+
+impl Display for TypeNameTree {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		self.write_to(f)
+	}
+}
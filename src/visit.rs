@@ -0,0 +1,160 @@
+//! Visiting the components of written type names.
+
+use crate::{tokens::is_ident_char, FmtResult, TypeName};
+use alloc::string::String;
+use core::fmt::Write;
+
+/// Visits the components of a type name.
+///
+/// This is a structured alternative to writing the flat name, e.g. for
+/// colored terminal or HTML output. See [`visit_type_name`].
+pub trait TypeNameVisitor {
+	/// Called for a name that is followed by generic arguments, e.g. `Vec`
+	/// of `Vec<u8>`, before its arguments are visited.
+	fn enter_generic(&mut self, name: &str);
+
+	/// Called after the generic arguments of the last entered name.
+	fn exit_generic(&mut self);
+
+	/// Called for the comma separating generic arguments, tuple elements
+	/// or function parameters.
+	fn separator(&mut self);
+
+	/// Called for identifiers, keywords and numbers that are not followed
+	/// by generic arguments, e.g. `u8`, `mut` or the `4` of `[u8; 4]`.
+	fn leaf(&mut self, name: &str);
+
+	/// Called for the remaining punctuation and whitespace, e.g. the `&`,
+	/// `(` or `; ` of references, tuples and arrays.
+	///
+	/// Does nothing by default.
+	fn punct(&mut self, _punct: &str) {}
+}
+
+/// Visits the components of the name of the given type with `visitor`.
+///
+/// Writing the visited components through the callbacks in order, with
+/// `<` and `>` for entered generics and `, ` for separators, yields the
+/// name written by [`TypeName::write_type_name`].
+///
+/// # Example
+///
+/// ```
+/// # use tyname::{visit_type_name, TypeNameVisitor};
+/// #[derive(Default)]
+/// struct Leaves(Vec<String>);
+///
+/// impl TypeNameVisitor for Leaves {
+///     fn enter_generic(&mut self, _name: &str) {}
+///     fn exit_generic(&mut self) {}
+///     fn separator(&mut self) {}
+///     fn leaf(&mut self, name: &str) {
+///         self.0.push(String::from(name));
+///     }
+/// }
+///
+/// let mut leaves = Leaves::default();
+/// visit_type_name::<Vec<(u8, &str)>, _>(&mut leaves);
+/// assert_eq!(leaves.0, ["u8", "str"]);
+/// ```
+pub fn visit_type_name<T, V>(visitor: &mut V)
+where
+	T: TypeName + ?Sized,
+	V: TypeNameVisitor + ?Sized,
+{
+	let mut writer = VisitWriter {
+		visitor,
+		ident: String::new(),
+		comma: false,
+		last: '\0',
+	};
+	T::write_type_name(&mut writer)
+		.expect("[tyname::visit_type_name] Encountered error while writing type name");
+	writer.flush();
+}
+
+/// A writer that splits the written name into the callbacks of a visitor.
+struct VisitWriter<'a, V>
+where
+	V: TypeNameVisitor + ?Sized,
+{
+	visitor: &'a mut V,
+	/// The identifier written so far which may be split across writes.
+	ident: String,
+	/// Whether the last written character is a comma not yet visited.
+	comma: bool,
+	/// The last written character.
+	last: char,
+}
+
+impl<'a, V> VisitWriter<'a, V>
+where
+	V: TypeNameVisitor + ?Sized,
+{
+	/// Visits the pending identifier, if any, as leaf.
+	fn flush_leaf(&mut self) {
+		if !self.ident.is_empty() {
+			self.visitor.leaf(&self.ident);
+			self.ident.clear();
+		}
+	}
+
+	/// Visits the pending comma, if any, as punctuation.
+	///
+	/// A comma that is not followed by a space, e.g. the trailing comma
+	/// of `(u8,)`, is no separator.
+	fn flush_comma(&mut self) {
+		if self.comma {
+			self.visitor.punct(",");
+			self.comma = false;
+		}
+	}
+
+	/// Visits the pending identifier or comma, if any.
+	fn flush(&mut self) {
+		self.flush_leaf();
+		self.flush_comma();
+	}
+}
+
+impl<'a, V> Write for VisitWriter<'a, V>
+where
+	V: TypeNameVisitor + ?Sized,
+{
+	fn write_str(&mut self, s: &str) -> FmtResult {
+		for c in s.chars() {
+			if self.comma && c == ' ' {
+				self.visitor.separator();
+				self.comma = false;
+				self.last = c;
+				continue
+			}
+			match c {
+				c if is_ident_char(c) => {
+					self.flush_comma();
+					self.ident.push(c);
+				}
+				'<' => {
+					self.flush_comma();
+					self.visitor.enter_generic(&self.ident);
+					self.ident.clear();
+				}
+				// Note: The `>` of the `->` of function pointers closes nothing.
+				'>' if self.last != '-' => {
+					self.flush();
+					self.visitor.exit_generic();
+				}
+				',' => {
+					self.flush();
+					self.comma = true;
+				}
+				c => {
+					self.flush();
+					self.visitor.punct(c.encode_utf8(&mut [0; 4]));
+				}
+			}
+			self.last = c;
+		}
+		Ok(())
+	}
+}
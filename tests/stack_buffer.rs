@@ -0,0 +1,56 @@
+//! Writes type names into a fixed stack buffer without any heap allocation.
+
+use tyname::TypeName;
+use core::fmt::{Error, Write};
+
+/// A writer into a fixed-size buffer that errors when it is full.
+struct StackWriter {
+	buffer: [u8; 32],
+	len: usize,
+}
+
+impl StackWriter {
+	fn new() -> Self {
+		StackWriter { buffer: [0; 32], len: 0 }
+	}
+
+	fn as_str(&self) -> &str {
+		core::str::from_utf8(&self.buffer[..self.len]).unwrap()
+	}
+}
+
+impl Write for StackWriter {
+	fn write_str(&mut self, s: &str) -> core::fmt::Result {
+		let end = self.len + s.len();
+		if end > self.buffer.len() {
+			return Err(Error)
+		}
+		self.buffer[self.len..end].copy_from_slice(s.as_bytes());
+		self.len = end;
+		Ok(())
+	}
+}
+
+fn assert_stack_type_name<T>(expected: &str)
+where
+	T: TypeName + ?Sized
+{
+	let mut writer = StackWriter::new();
+	T::write_type_name(&mut writer).unwrap();
+	assert_eq!(writer.as_str(), expected);
+}
+
+#[test]
+fn core_types() {
+	assert_stack_type_name::<[u8; 4]>("[u8; 4]");
+	assert_stack_type_name::<(u8, bool)>("(u8, bool)");
+	assert_stack_type_name::<&mut [(i8, char); 2]>("&mut [(i8, char); 2]");
+	assert_stack_type_name::<*const str>("*const str");
+}
+
+#[test]
+fn full_buffer() {
+	let mut writer = StackWriter::new();
+	assert_eq!(<[[u8; 32]; 32]>::write_type_name(&mut writer), Ok(()));
+	assert_eq!(<([[u64; 32]; 32], [[u64; 32]; 32])>::write_type_name(&mut writer), Err(Error));
+}